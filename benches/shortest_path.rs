@@ -0,0 +1,75 @@
+//! 1000x1000 のグリッド (10^6 節点) で最短経路アルゴリズムの実行時間を比べる．
+//! `cargo bench --bench shortest_path` で実行する．
+
+use comp_prog_lib_rs::graph::astar::astar;
+use comp_prog_lib_rs::graph::dijkstra::Dijkstra;
+use comp_prog_lib_rs::graph::{adjacent4, Graph};
+use std::time::Instant;
+
+const HEIGHT: usize = 1000;
+const WIDTH: usize = 1000;
+
+/// 各マスに 1 以上 9 以下の重みを決定的な疑似乱数で割り当て，
+/// 隣接するマスへ移動先の重みのコストで移動できるグラフを作る．
+fn grid_graph() -> Graph<usize> {
+    let mut state = 88172645463325252u64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let cells = (0..HEIGHT)
+        .map(|_| {
+            (0..WIDTH)
+                .map(|_| (next() % 9 + 1) as usize)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut graph = Graph::directed(HEIGHT * WIDTH);
+    for row in 0..HEIGHT {
+        for col in 0..WIDTH {
+            for (nr, nc) in adjacent4(&cells, row, col) {
+                graph.add_edge(row * WIDTH + col, nr * WIDTH + nc, cells[nr][nc]);
+            }
+        }
+    }
+    graph
+}
+
+fn bench<F>(name: &str, f: F)
+where
+    F: FnOnce() -> Option<usize>,
+{
+    let now = Instant::now();
+    let distance = f();
+    println!(
+        "{:<24} {:>8.1?} (distance = {:?})",
+        name,
+        now.elapsed(),
+        distance
+    );
+}
+
+fn main() {
+    let graph = grid_graph();
+    let goal = HEIGHT * WIDTH - 1;
+
+    let mut dijkstra = Dijkstra::from(graph.clone());
+    bench("Dijkstra (BinaryHeap)", || {
+        dijkstra.solve(0);
+        dijkstra.distance(goal)
+    });
+    bench("Dial", || {
+        dijkstra.solve_with_buckets(0);
+        dijkstra.distance(goal)
+    });
+    bench("Dijkstra (RadixHeap)", || {
+        dijkstra.solve_with_radix_heap(0);
+        dijkstra.distance(goal)
+    });
+
+    // 各マスの重みは 1 以上なので，マンハッタン距離は許容的かつ無矛盾なヒューリスティックになる．
+    let heuristic = |v: usize| (HEIGHT - 1 - v / WIDTH) + (WIDTH - 1 - v % WIDTH);
+    bench("A*", || astar(&graph, 0, goal, heuristic));
+}
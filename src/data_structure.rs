@@ -1,2 +1,7 @@
+pub mod dynamic_connectivity;
+pub mod fenwick;
+pub mod radix_heap;
 pub mod segment_tree;
+pub mod segment_tree_beats;
+pub mod sparse_table;
 pub mod unionfind;
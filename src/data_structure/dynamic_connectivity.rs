@@ -0,0 +1,193 @@
+use crate::data_structure::unionfind::RollbackUnionFind;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug)]
+enum Operation {
+    Update,
+    Same(usize, usize),
+}
+
+/// 辺の追加・削除と二頂点の連結性判定のクエリを先読みして処理する．
+/// 各辺が存在する時間区間を時間軸上のセグメント木に載せ，`RollbackUnionFind` で
+/// 辺を張っては戻しながら木を走査する．`q` をクエリ数として `O(q log q log n)`．
+pub struct OfflineDynamicConnectivity {
+    size: usize,
+    operations: Vec<Operation>,
+    // 辺 `(u, v)` (`u <= v`) を張った時刻のうち，まだ削除されていないもの．
+    alive: HashMap<(usize, usize), Vec<usize>>,
+    // 辺 `(u, v)` が時刻 `[start, end)` に存在していたことを表す．
+    intervals: Vec<(usize, usize, usize, usize)>,
+}
+
+impl OfflineDynamicConnectivity {
+    /// 頂点数 `size` の辺のないグラフで初期化する．
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            operations: vec![],
+            alive: HashMap::new(),
+            intervals: vec![],
+        }
+    }
+
+    /// 頂点 `u` と `v` を結ぶ辺を追加する．多重辺も許す．
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        let now = self.operations.len();
+        self.alive.entry(Self::key(u, v)).or_default().push(now);
+        self.operations.push(Operation::Update);
+    }
+
+    /// 頂点 `u` と `v` を結ぶ辺を一本削除する．
+    /// そのような辺が存在しない場合は panic する．
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        let now = self.operations.len();
+        let (u, v) = Self::key(u, v);
+        let start = self
+            .alive
+            .get_mut(&(u, v))
+            .and_then(|starts| starts.pop())
+            .expect("the edge to remove does not exist");
+        self.intervals.push((u, v, start, now));
+        self.operations.push(Operation::Update);
+    }
+
+    /// その時点で頂点 `u` と `v` が連結かどうかを問うクエリを追加する．
+    pub fn same(&mut self, u: usize, v: usize) {
+        self.operations.push(Operation::Same(u, v));
+    }
+
+    /// `same` で追加したクエリの答えを追加した順に返す．
+    pub fn solve(&self) -> Vec<bool> {
+        let n_times = self.operations.len();
+        if n_times == 0 {
+            return vec![];
+        }
+        let n_leaves = n_times.next_power_of_two();
+        let mut edges = vec![vec![]; 2 * n_leaves - 1];
+        let remaining = self
+            .alive
+            .iter()
+            .flat_map(|(&(u, v), starts)| starts.iter().map(move |&start| (u, v, start, n_times)));
+        for (u, v, start, end) in self.intervals.iter().cloned().chain(remaining) {
+            Self::insert(&mut edges, (u, v), start, end, 0, 0, n_leaves);
+        }
+
+        let mut uf = RollbackUnionFind::new(self.size);
+        let mut answers = vec![];
+        self.dfs(&edges, &mut uf, &mut answers, 0, 0, n_leaves);
+        answers
+    }
+
+    fn key(u: usize, v: usize) -> (usize, usize) {
+        (u.min(v), u.max(v))
+    }
+
+    fn insert(
+        edges: &mut [Vec<(usize, usize)>],
+        edge: (usize, usize),
+        start: usize,
+        end: usize,
+        i: usize,
+        left: usize,
+        right: usize,
+    ) {
+        if right <= start || end <= left {
+            return;
+        }
+        if start <= left && right <= end {
+            edges[i].push(edge);
+            return;
+        }
+        let mid = (left + right) / 2;
+        Self::insert(edges, edge, start, end, 2 * i + 1, left, mid);
+        Self::insert(edges, edge, start, end, 2 * i + 2, mid, right);
+    }
+
+    fn dfs(
+        &self,
+        edges: &[Vec<(usize, usize)>],
+        uf: &mut RollbackUnionFind,
+        answers: &mut Vec<bool>,
+        i: usize,
+        left: usize,
+        right: usize,
+    ) {
+        if left >= self.operations.len() {
+            return;
+        }
+        let snapshot = uf.snapshot();
+        for &(u, v) in &edges[i] {
+            uf.unite(u, v);
+        }
+        if right - left == 1 {
+            if let Operation::Same(u, v) = self.operations[left] {
+                answers.push(uf.same(u, v));
+            }
+        } else {
+            let mid = (left + right) / 2;
+            self.dfs(edges, uf, answers, 2 * i + 1, left, mid);
+            self.dfs(edges, uf, answers, 2 * i + 2, mid, right);
+        }
+        uf.rollback(snapshot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_structure::unionfind::UnionFind;
+    use proptest::strategy::{Just, Strategy};
+
+    #[test]
+    fn test_add_and_remove_edges() {
+        let mut dc = OfflineDynamicConnectivity::new(3);
+        dc.add_edge(0, 1);
+        dc.add_edge(1, 2);
+        dc.same(0, 2);
+        dc.remove_edge(1, 0);
+        dc.same(0, 2);
+        dc.same(1, 2);
+        dc.add_edge(2, 0);
+        dc.same(0, 1);
+        assert_eq!(dc.solve(), vec![true, false, true, true]);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_dynamic_connectivity_random(
+            (n, queries) in (1..10usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..3usize, 0..n, 0..n), 1..60))
+            })
+        ) {
+            let mut dc = OfflineDynamicConnectivity::new(n);
+            let mut edges: Vec<(usize, usize)> = vec![];
+            let mut expected = vec![];
+            for (kind, u, v) in queries {
+                match kind {
+                    0 => {
+                        dc.add_edge(u, v);
+                        edges.push((u, v));
+                    }
+                    1 => {
+                        if let Some(i) = edges
+                            .iter()
+                            .position(|&(x, y)| (x, y) == (u, v) || (x, y) == (v, u))
+                        {
+                            dc.remove_edge(u, v);
+                            edges.swap_remove(i);
+                        }
+                    }
+                    _ => {
+                        dc.same(u, v);
+                        let mut uf = UnionFind::new(n);
+                        for &(x, y) in &edges {
+                            uf.unite(x, y);
+                        }
+                        expected.push(uf.same(u, v));
+                    }
+                }
+            }
+            assert_eq!(dc.solve(), expected);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::ops::{Add, Mul, Sub};
+
+/// 一点加算と区間和を `O(log n)` で求める Fenwick Tree (Binary Indexed Tree)．
+/// `T` は加法について群をなす型 (整数型や `ModInt` など)．
+/// 添字は 0-indexed で，区間は半開区間 `[start, end)` で指定する．
+#[derive(Clone, Debug)]
+pub struct Fenwick<T> {
+    // 内部では 1-indexed で持つ．`tree[0]` は使わない．
+    tree: Vec<T>,
+}
+
+impl<T> Fenwick<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + num::Zero,
+{
+    /// 要素数 `n` で全要素が 0 の木を作る．
+    pub fn new(n: usize) -> Self {
+        Self {
+            tree: vec![T::zero(); n + 1],
+        }
+    }
+
+    /// 要素数を返す．
+    pub fn len(&self) -> usize {
+        self.tree.len() - 1
+    }
+
+    /// 要素を持たないかどうかを返す．
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `i` 番目の要素に `x` を加える．`O(log n)`．
+    pub fn add(&mut self, i: usize, x: T) {
+        let mut i = i + 1;
+        while i < self.tree.len() {
+            self.tree[i] = self.tree[i] + x;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// 区間 `[0, end)` の和を返す．`O(log n)`．
+    pub fn prefix_sum(&self, end: usize) -> T {
+        let mut i = end;
+        let mut result = T::zero();
+        while i > 0 {
+            result = result + self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        result
+    }
+
+    /// 区間 `[start, end)` の和を返す．`O(log n)`．
+    pub fn range_sum(&self, start: usize, end: usize) -> T {
+        self.prefix_sum(end) - self.prefix_sum(start)
+    }
+}
+
+impl<T> Fenwick<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + num::Zero + PartialOrd,
+{
+    /// `prefix_sum(i + 1) >= sum` となる最小の `i` を二分探索で返す．
+    /// そのような `i` が存在しなければ `len()` を返す．
+    /// 全要素が非負であることを仮定する．`O(log n)`．
+    pub fn lower_bound(&self, sum: T) -> usize {
+        let n = self.len();
+        let mut pos = 0;
+        let mut acc = T::zero();
+        let mut step = if n == 0 { 0 } else { 1 << n.ilog2() };
+        while step > 0 {
+            if pos + step <= n && acc + self.tree[pos + step] < sum {
+                pos += step;
+                acc = acc + self.tree[pos];
+            }
+            step >>= 1;
+        }
+        pos
+    }
+}
+
+impl<T> FromIterator<T> for Fenwick<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + num::Zero,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let v: Vec<T> = iter.into_iter().collect();
+        v.into()
+    }
+}
+
+impl<T> From<Vec<T>> for Fenwick<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + num::Zero,
+{
+    /// `O(n)` で構築する．
+    fn from(v: Vec<T>) -> Self {
+        let mut tree = vec![T::zero(); v.len() + 1];
+        tree[1..].copy_from_slice(&v);
+        for i in 1..tree.len() {
+            let parent = i + (i & i.wrapping_neg());
+            if parent < tree.len() {
+                tree[parent] = tree[parent] + tree[i];
+            }
+        }
+        Self { tree }
+    }
+}
+
+/// 区間加算と区間和を `O(log n)` で求める Fenwick Tree．
+/// 二本の `Fenwick` を用いて，`prefix_sum(i) = i * b1.prefix_sum(i) - b2.prefix_sum(i)`
+/// となるように管理する．
+#[derive(Clone, Debug)]
+pub struct RangeAddFenwick<T> {
+    b1: Fenwick<T>,
+    b2: Fenwick<T>,
+}
+
+impl<T> RangeAddFenwick<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + num::Zero + num::FromPrimitive,
+{
+    /// 要素数 `n` で全要素が 0 の木を作る．
+    pub fn new(n: usize) -> Self {
+        Self {
+            b1: Fenwick::new(n),
+            b2: Fenwick::new(n),
+        }
+    }
+
+    /// 要素数を返す．
+    pub fn len(&self) -> usize {
+        self.b1.len()
+    }
+
+    /// 要素を持たないかどうかを返す．
+    pub fn is_empty(&self) -> bool {
+        self.b1.is_empty()
+    }
+
+    /// 区間 `[start, end)` の各要素に `x` を加える．`O(log n)`．
+    pub fn add(&mut self, start: usize, end: usize, x: T) {
+        let zero = T::zero();
+        if start < self.len() {
+            self.b1.add(start, x);
+            self.b2.add(start, x * Self::index(start));
+        }
+        if end < self.len() {
+            self.b1.add(end, zero - x);
+            self.b2.add(end, zero - x * Self::index(end));
+        }
+    }
+
+    /// 区間 `[0, end)` の和を返す．`O(log n)`．
+    pub fn prefix_sum(&self, end: usize) -> T {
+        self.b1.prefix_sum(end) * Self::index(end) - self.b2.prefix_sum(end)
+    }
+
+    /// 区間 `[start, end)` の和を返す．`O(log n)`．
+    pub fn range_sum(&self, start: usize, end: usize) -> T {
+        self.prefix_sum(end) - self.prefix_sum(start)
+    }
+
+    fn index(i: usize) -> T {
+        T::from_usize(i).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::ModInt;
+    use proptest::strategy::{Just, Strategy};
+
+    proptest::proptest! {
+        #[test]
+        fn test_fenwick_range_sum_i64(v in proptest::collection::vec(-100..100i64, 0..100)) {
+            let fw = v.iter().cloned().collect::<Fenwick<_>>();
+            for i in 0..=v.len() {
+                for j in i..=v.len() {
+                    assert_eq!(fw.range_sum(i, j), v[i..j].iter().sum::<i64>());
+                }
+            }
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_fenwick_add_modint(
+            (n, queries) in (1..50usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..1_000_000_007isize), 1..50))
+            })
+        ) {
+            let mut fw = Fenwick::new(n);
+            let mut v = vec![ModInt::new(0); n];
+            for (i, x) in queries {
+                fw.add(i, ModInt::new(x));
+                v[i] += ModInt::new(x);
+            }
+            for i in 0..=n {
+                let expected = v[..i].iter().fold(ModInt::new(0), |acc, &x| acc + x);
+                assert_eq!(fw.prefix_sum(i), expected);
+            }
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_fenwick_lower_bound(
+            v in proptest::collection::vec(0..10u64, 0..100),
+            sum in 0..500u64,
+        ) {
+            let fw = v.iter().cloned().collect::<Fenwick<_>>();
+            let expected = (0..v.len())
+                .find(|&i| v[..=i].iter().sum::<u64>() >= sum)
+                .unwrap_or(v.len());
+            assert_eq!(fw.lower_bound(sum), expected);
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_range_add_fenwick_i64(
+            (n, queries) in (1..50usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..=n, 0..=n, -100..100i64), 1..50))
+            })
+        ) {
+            let mut fw = RangeAddFenwick::new(n);
+            let mut v = vec![0i64; n];
+            for (a, b, x) in queries {
+                let (start, end) = (a.min(b), a.max(b));
+                fw.add(start, end, x);
+                v[start..end].iter_mut().for_each(|e| *e += x);
+            }
+            for i in 0..=n {
+                for j in i..=n {
+                    assert_eq!(fw.range_sum(i, j), v[i..j].iter().sum::<i64>());
+                }
+            }
+        }
+    }
+}
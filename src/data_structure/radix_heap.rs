@@ -0,0 +1,104 @@
+/// キーが `u64` の単調な最小ヒープ．
+/// 最後に取り出したキー以上のキーしか追加できない代わりに，`push` は `O(1)`，
+/// `pop` はならし `O(log C)` (`C` はキーの最大値) で `BinaryHeap` より速い．
+#[derive(Clone, Debug)]
+pub struct RadixHeap<T> {
+    // `buckets[i]` には，最後に取り出したキーとの排他的論理和の最上位ビットが `i - 1` 番目である
+    // キーを持つ要素が入る．`buckets[0]` には最後に取り出したキーと等しいキーを持つ要素が入る．
+    buckets: Vec<Vec<(u64, T)>>,
+    last: u64,
+    len: usize,
+}
+
+impl<T> RadixHeap<T> {
+    /// 空のヒープを作る．
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..=u64::BITS).map(|_| vec![]).collect(),
+            last: 0,
+            len: 0,
+        }
+    }
+
+    /// 要素数を返す．
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 要素を持たないかどうかを返す．
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// キー `key` で `value` を追加する．`key` は最後に取り出したキー以上でなければならない．
+    pub fn push(&mut self, key: u64, value: T) {
+        assert!(
+            key >= self.last,
+            "key must not be less than the last popped key"
+        );
+        let i = Self::bucket(key, self.last);
+        self.buckets[i].push((key, value));
+        self.len += 1;
+    }
+
+    /// キーが最小の要素を取り出す．
+    pub fn pop(&mut self) -> Option<(u64, T)> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.buckets[0].is_empty() {
+            let i = self.buckets.iter().position(|b| !b.is_empty()).unwrap();
+            self.last = self.buckets[i].iter().map(|&(key, _)| key).min().unwrap();
+            for (key, value) in std::mem::take(&mut self.buckets[i]) {
+                let j = Self::bucket(key, self.last);
+                self.buckets[j].push((key, value));
+            }
+        }
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+
+    fn bucket(key: u64, last: u64) -> usize {
+        (u64::BITS - (key ^ last).leading_zeros()) as usize
+    }
+}
+
+impl<T> Default for RadixHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    proptest::proptest! {
+        #[test]
+        fn test_radix_heap_random(queries in proptest::collection::vec(proptest::option::of(0..100u64), 1..100)) {
+            // `None` は pop を，`Some(d)` は最後に取り出したキーに d を足したキーの push を表す．
+            let mut radix_heap = RadixHeap::new();
+            let mut bin_heap = BinaryHeap::new();
+            let mut last = 0;
+            for (i, query) in queries.into_iter().enumerate() {
+                match query {
+                    Some(d) => {
+                        radix_heap.push(last + d, i);
+                        bin_heap.push(Reverse((last + d, i)));
+                    }
+                    None => {
+                        let expected = bin_heap.pop().map(|Reverse((key, _))| key);
+                        let actual = radix_heap.pop().map(|(key, _)| key);
+                        assert_eq!(actual, expected);
+                        if let Some(key) = actual {
+                            last = key;
+                        }
+                    }
+                }
+                assert_eq!(radix_heap.len(), bin_heap.len());
+            }
+        }
+    }
+}
@@ -25,7 +25,7 @@ where
             i = (i - 1) / 2;
             let left_child = &self.nodes[2 * i + 1];
             let right_child = &self.nodes[2 * i + 2];
-            self.nodes[i] = left_child.mappend(&right_child);
+            self.nodes[i] = left_child.mappend(right_child);
         }
     }
 
@@ -84,7 +84,7 @@ mod tests {
             let st = v.iter().cloned().map(crate::math::monoid::Max).collect::<SegmentTree<_>>();
             for i in 0..v.len() {
                 for j in i..v.len() {
-                    let max = (i..j).map(|k| v[k]).max().unwrap_or(std::i64::MIN);
+                    let max = (i..j).map(|k| v[k]).max().unwrap_or(i64::MIN);
                     assert_eq!(st.query(i, j).0, max);
                 }
             }
@@ -97,7 +97,7 @@ mod tests {
             let st = v.iter().cloned().map(crate::math::monoid::Max).collect::<SegmentTree<_>>();
             for i in 0..v.len() {
                 for j in i..v.len() {
-                    let max = (i..j).map(|k| v[k]).max().unwrap_or(std::u64::MIN);
+                    let max = (i..j).map(|k| v[k]).max().unwrap_or(u64::MIN);
                     assert_eq!(st.query(i, j).0, max);
                 }
             }
@@ -110,7 +110,7 @@ mod tests {
             let st = v.iter().cloned().map(crate::math::monoid::Min).collect::<SegmentTree<_>>();
             for i in 0..v.len() {
                 for j in i..v.len() {
-                    let min = (i..j).map(|k| v[k]).min().unwrap_or(std::i64::MAX);
+                    let min = (i..j).map(|k| v[k]).min().unwrap_or(i64::MAX);
                     assert_eq!(st.query(i, j).0, min);
                 }
             }
@@ -123,7 +123,7 @@ mod tests {
             let st = v.iter().cloned().map(crate::math::monoid::Min).collect::<SegmentTree<_>>();
             for i in 0..v.len() {
                 for j in i..v.len() {
-                    let min = (i..j).map(|k| v[k]).min().unwrap_or(std::u64::MAX);
+                    let min = (i..j).map(|k| v[k]).min().unwrap_or(u64::MAX);
                     assert_eq!(st.query(i, j).0, min);
                 }
             }
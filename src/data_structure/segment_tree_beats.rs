@@ -0,0 +1,346 @@
+/// Segment Tree Beats の各節点が持つ情報．
+/// 要素を持たない葉は `len == 0` で表し，最大値・最小値には番兵を入れておく．
+#[derive(Clone, Debug)]
+struct Node {
+    max: i64,
+    second_max: i64,
+    max_count: i64,
+    min: i64,
+    second_min: i64,
+    min_count: i64,
+    sum: i64,
+    len: i64,
+    lazy_add: i64,
+}
+
+impl Node {
+    fn empty() -> Self {
+        Self {
+            max: i64::MIN,
+            second_max: i64::MIN,
+            max_count: 0,
+            min: i64::MAX,
+            second_min: i64::MAX,
+            min_count: 0,
+            sum: 0,
+            len: 0,
+            lazy_add: 0,
+        }
+    }
+
+    fn leaf(x: i64) -> Self {
+        Self {
+            max: x,
+            second_max: i64::MIN,
+            max_count: 1,
+            min: x,
+            second_min: i64::MAX,
+            min_count: 1,
+            sum: x,
+            len: 1,
+            lazy_add: 0,
+        }
+    }
+
+    fn merge(left: &Self, right: &Self) -> Self {
+        let (max, second_max, max_count) = if left.max == right.max {
+            (
+                left.max,
+                left.second_max.max(right.second_max),
+                left.max_count + right.max_count,
+            )
+        } else if left.max > right.max {
+            (left.max, left.second_max.max(right.max), left.max_count)
+        } else {
+            (right.max, left.max.max(right.second_max), right.max_count)
+        };
+        let (min, second_min, min_count) = if left.min == right.min {
+            (
+                left.min,
+                left.second_min.min(right.second_min),
+                left.min_count + right.min_count,
+            )
+        } else if left.min < right.min {
+            (left.min, left.second_min.min(right.min), left.min_count)
+        } else {
+            (right.min, left.min.min(right.second_min), right.min_count)
+        };
+        Self {
+            max,
+            second_max,
+            max_count,
+            min,
+            second_min,
+            min_count,
+            sum: left.sum + right.sum,
+            len: left.len + right.len,
+            lazy_add: 0,
+        }
+    }
+
+    fn apply_add(&mut self, x: i64) {
+        if self.len == 0 {
+            return;
+        }
+        self.sum += x * self.len;
+        self.max += x;
+        if self.second_max != i64::MIN {
+            self.second_max += x;
+        }
+        self.min += x;
+        if self.second_min != i64::MAX {
+            self.second_min += x;
+        }
+        self.lazy_add += x;
+    }
+
+    /// `second_max < x < max` のときに限り呼び出せる．
+    fn apply_chmin(&mut self, x: i64) {
+        self.sum += (x - self.max) * self.max_count;
+        if self.max == self.min {
+            self.min = x;
+        } else if self.max == self.second_min {
+            self.second_min = x;
+        }
+        self.max = x;
+    }
+
+    /// `min < x < second_min` のときに限り呼び出せる．
+    fn apply_chmax(&mut self, x: i64) {
+        self.sum += (x - self.min) * self.min_count;
+        if self.min == self.max {
+            self.max = x;
+        } else if self.min == self.second_max {
+            self.second_max = x;
+        }
+        self.min = x;
+    }
+}
+
+/// 区間 chmin，区間 chmax，区間加算と区間和・区間最大値・区間最小値の取得を
+/// ならし `O(log^2 n)` で行う Segment Tree Beats．
+/// 区間はすべて半開区間 `[start, end)` で指定する．
+/// `i64::MIN` と `i64::MAX` は番兵として使うので要素の値として用いてはならない．
+#[derive(Clone, Debug)]
+pub struct SegmentTreeBeats {
+    n_leaves: usize,
+    nodes: Vec<Node>,
+}
+
+impl SegmentTreeBeats {
+    /// 要素数 `n` で全要素が 0 の木を作る．
+    pub fn new(n: usize) -> Self {
+        vec![0; n].into()
+    }
+
+    /// 区間 `[start, end)` の各要素 `a_i` を `min(a_i, x)` で置き換える．
+    pub fn chmin(&mut self, start: usize, end: usize, x: i64) {
+        self.chmin_rec(start, end, x, 0, 0, self.n_leaves);
+    }
+
+    /// 区間 `[start, end)` の各要素 `a_i` を `max(a_i, x)` で置き換える．
+    pub fn chmax(&mut self, start: usize, end: usize, x: i64) {
+        self.chmax_rec(start, end, x, 0, 0, self.n_leaves);
+    }
+
+    /// 区間 `[start, end)` の各要素に `x` を加える．
+    pub fn add(&mut self, start: usize, end: usize, x: i64) {
+        self.add_rec(start, end, x, 0, 0, self.n_leaves);
+    }
+
+    /// 区間 `[start, end)` の和を返す．空区間なら 0．
+    pub fn sum(&mut self, start: usize, end: usize) -> i64 {
+        self.query_rec(start, end, 0, 0, self.n_leaves).sum
+    }
+
+    /// 区間 `[start, end)` の最大値を返す．空区間なら `i64::MIN`．
+    pub fn max(&mut self, start: usize, end: usize) -> i64 {
+        self.query_rec(start, end, 0, 0, self.n_leaves).max
+    }
+
+    /// 区間 `[start, end)` の最小値を返す．空区間なら `i64::MAX`．
+    pub fn min(&mut self, start: usize, end: usize) -> i64 {
+        self.query_rec(start, end, 0, 0, self.n_leaves).min
+    }
+
+    fn pull(&mut self, i: usize) {
+        self.nodes[i] = Node::merge(&self.nodes[2 * i + 1], &self.nodes[2 * i + 2]);
+    }
+
+    fn push(&mut self, i: usize) {
+        let (max, min, lazy_add) = {
+            let node = &self.nodes[i];
+            (node.max, node.min, node.lazy_add)
+        };
+        for child in [2 * i + 1, 2 * i + 2] {
+            let child = &mut self.nodes[child];
+            if lazy_add != 0 {
+                child.apply_add(lazy_add);
+            }
+            if child.len > 0 && child.max > max {
+                child.apply_chmin(max);
+            }
+            if child.len > 0 && child.min < min {
+                child.apply_chmax(min);
+            }
+        }
+        self.nodes[i].lazy_add = 0;
+    }
+
+    fn chmin_rec(&mut self, start: usize, end: usize, x: i64, i: usize, left: usize, right: usize) {
+        if right <= start || end <= left || self.nodes[i].max <= x {
+            return;
+        }
+        if start <= left && right <= end && self.nodes[i].second_max < x {
+            self.nodes[i].apply_chmin(x);
+            return;
+        }
+        self.push(i);
+        let mid = (left + right) / 2;
+        self.chmin_rec(start, end, x, 2 * i + 1, left, mid);
+        self.chmin_rec(start, end, x, 2 * i + 2, mid, right);
+        self.pull(i);
+    }
+
+    fn chmax_rec(&mut self, start: usize, end: usize, x: i64, i: usize, left: usize, right: usize) {
+        if right <= start || end <= left || self.nodes[i].min >= x {
+            return;
+        }
+        if start <= left && right <= end && self.nodes[i].second_min > x {
+            self.nodes[i].apply_chmax(x);
+            return;
+        }
+        self.push(i);
+        let mid = (left + right) / 2;
+        self.chmax_rec(start, end, x, 2 * i + 1, left, mid);
+        self.chmax_rec(start, end, x, 2 * i + 2, mid, right);
+        self.pull(i);
+    }
+
+    fn add_rec(&mut self, start: usize, end: usize, x: i64, i: usize, left: usize, right: usize) {
+        if right <= start || end <= left {
+            return;
+        }
+        if start <= left && right <= end {
+            self.nodes[i].apply_add(x);
+            return;
+        }
+        self.push(i);
+        let mid = (left + right) / 2;
+        self.add_rec(start, end, x, 2 * i + 1, left, mid);
+        self.add_rec(start, end, x, 2 * i + 2, mid, right);
+        self.pull(i);
+    }
+
+    fn query_rec(&mut self, start: usize, end: usize, i: usize, left: usize, right: usize) -> Node {
+        if right <= start || end <= left {
+            Node::empty()
+        } else if start <= left && right <= end {
+            self.nodes[i].clone()
+        } else {
+            self.push(i);
+            let mid = (left + right) / 2;
+            let left_child = self.query_rec(start, end, 2 * i + 1, left, mid);
+            let right_child = self.query_rec(start, end, 2 * i + 2, mid, right);
+            Node::merge(&left_child, &right_child)
+        }
+    }
+}
+
+impl FromIterator<i64> for SegmentTreeBeats {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = i64>,
+    {
+        let v: Vec<i64> = iter.into_iter().collect();
+        v.into()
+    }
+}
+
+impl From<Vec<i64>> for SegmentTreeBeats {
+    fn from(v: Vec<i64>) -> Self {
+        let n_leaves = v.len().next_power_of_two();
+        let mut nodes = vec![Node::empty(); 2 * n_leaves - 1];
+        for (i, x) in v.into_iter().enumerate() {
+            nodes[i + n_leaves - 1] = Node::leaf(x);
+        }
+        let mut st = SegmentTreeBeats { n_leaves, nodes };
+        for i in (0..n_leaves - 1).rev() {
+            st.pull(i);
+        }
+        st
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[derive(Clone, Debug)]
+    enum Query {
+        Chmin(usize, usize, i64),
+        Chmax(usize, usize, i64),
+        Add(usize, usize, i64),
+    }
+
+    fn query_strategy(n: usize) -> impl Strategy<Value = Query> {
+        let range = (0..=n, 0..=n).prop_map(|(a, b)| (a.min(b), a.max(b)));
+        (0..3usize, range, -100..100i64).prop_map(|(kind, (start, end), x)| match kind {
+            0 => Query::Chmin(start, end, x),
+            1 => Query::Chmax(start, end, x),
+            _ => Query::Add(start, end, x),
+        })
+    }
+
+    fn case_strategy() -> impl Strategy<Value = (Vec<i64>, Vec<Query>)> {
+        (1..50usize).prop_flat_map(|n| {
+            (
+                proptest::collection::vec(-100..100i64, n),
+                proptest::collection::vec(query_strategy(n), 1..50),
+            )
+        })
+    }
+
+    #[test]
+    fn test_segment_tree_beats_chmin_then_sum() {
+        let mut st = SegmentTreeBeats::from(vec![5, 1, 4, 2, 3]);
+        st.chmin(0, 5, 3);
+        assert_eq!(st.sum(0, 5), 3 + 1 + 3 + 2 + 3);
+        assert_eq!(st.max(0, 5), 3);
+        st.chmax(1, 4, 2);
+        assert_eq!(st.sum(0, 5), 3 + 2 + 3 + 2 + 3);
+        assert_eq!(st.min(0, 5), 2);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_segment_tree_beats_random((mut v, queries) in case_strategy()) {
+            let mut st = v.iter().cloned().collect::<SegmentTreeBeats>();
+            for query in queries {
+                match query {
+                    Query::Chmin(start, end, x) => {
+                        st.chmin(start, end, x);
+                        v[start..end].iter_mut().for_each(|a| *a = (*a).min(x));
+                    }
+                    Query::Chmax(start, end, x) => {
+                        st.chmax(start, end, x);
+                        v[start..end].iter_mut().for_each(|a| *a = (*a).max(x));
+                    }
+                    Query::Add(start, end, x) => {
+                        st.add(start, end, x);
+                        v[start..end].iter_mut().for_each(|a| *a += x);
+                    }
+                }
+                assert_eq!(st.sum(0, v.len()), v.iter().sum::<i64>());
+            }
+            for i in 0..v.len() {
+                for j in i..=v.len() {
+                    assert_eq!(st.sum(i, j), v[i..j].iter().sum::<i64>());
+                    assert_eq!(st.max(i, j), v[i..j].iter().cloned().max().unwrap_or(i64::MIN));
+                    assert_eq!(st.min(i, j), v[i..j].iter().cloned().min().unwrap_or(i64::MAX));
+                }
+            }
+        }
+    }
+}
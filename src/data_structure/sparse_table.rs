@@ -0,0 +1,208 @@
+use crate::math::monoid::{Idempotent, Monoid};
+
+/// 冪等なモノイドについて，静的な配列の区間積を前処理 `O(n log n)`，クエリ `O(1)` で求める．
+pub struct SparseTable<T>
+where
+    T: Idempotent,
+{
+    // `table[k][i]` は区間 `[i, i + 2^k)` の積．
+    table: Vec<Vec<T>>,
+}
+
+impl<T> SparseTable<T>
+where
+    T: Idempotent,
+{
+    /// 区間 `[start, end)` の積を返す．空区間なら単位元を返す．
+    pub fn query(&self, start: usize, end: usize) -> T {
+        if start >= end {
+            return T::mempty();
+        }
+        let k = (end - start).ilog2() as usize;
+        self.table[k][start].mappend(&self.table[k][end - (1 << k)])
+    }
+}
+
+impl<T> FromIterator<T> for SparseTable<T>
+where
+    T: Idempotent,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let v: Vec<T> = iter.into_iter().collect();
+        v.into()
+    }
+}
+
+impl<T> From<Vec<T>> for SparseTable<T>
+where
+    T: Idempotent,
+{
+    fn from(v: Vec<T>) -> Self {
+        let n = v.len();
+        let mut table = vec![v];
+        let mut k = 1;
+        while (1 << k) <= n {
+            let prev = &table[k - 1];
+            let half = 1 << (k - 1);
+            let row = (0..=n - (1 << k))
+                .map(|i| prev[i].mappend(&prev[i + half]))
+                .collect();
+            table.push(row);
+            k += 1;
+        }
+        SparseTable { table }
+    }
+}
+
+/// 任意のモノイドについて，静的な配列の区間積を前処理 `O(n log n)`，クエリ `O(1)` で求める．
+/// 演算が可換である必要はなく，積は左から順に取られる．
+pub struct DisjointSparseTable<T>
+where
+    T: Monoid,
+{
+    // `table[k]` では，配列を長さ `2^k` のブロックに区切り，隣り合う二つのブロックの境界から
+    // 左右に向かって累積した積を持つ．
+    table: Vec<Vec<T>>,
+}
+
+impl<T> DisjointSparseTable<T>
+where
+    T: Monoid,
+{
+    /// 区間 `[start, end)` の積を返す．空区間なら単位元を返す．
+    pub fn query(&self, start: usize, end: usize) -> T {
+        if start >= end {
+            return T::mempty();
+        }
+        let last = end - 1;
+        if start == last {
+            return self.table[0][start].clone();
+        }
+        let k = (start ^ last).ilog2() as usize;
+        self.table[k][start].mappend(&self.table[k][last])
+    }
+}
+
+impl<T> FromIterator<T> for DisjointSparseTable<T>
+where
+    T: Monoid,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let v: Vec<T> = iter.into_iter().collect();
+        v.into()
+    }
+}
+
+impl<T> From<Vec<T>> for DisjointSparseTable<T>
+where
+    T: Monoid,
+{
+    fn from(v: Vec<T>) -> Self {
+        let n = v.len();
+        let levels = if n <= 1 {
+            1
+        } else {
+            (n - 1).ilog2() as usize + 1
+        };
+        let mut table = vec![v.clone(); levels];
+        for (k, row) in table.iter_mut().enumerate().skip(1) {
+            let half = 1 << k;
+            let mut center = half;
+            while center < n {
+                for i in (center - half..center - 1).rev() {
+                    row[i] = v[i].mappend(&row[i + 1]);
+                }
+                for i in center + 1..(center + half).min(n) {
+                    row[i] = row[i - 1].mappend(&v[i]);
+                }
+                center += 2 * half;
+            }
+        }
+        DisjointSparseTable { table }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::monoid::{Max, Min};
+
+    /// 非可換なモノイドの例として，一次関数 `x -> ax + b` の合成を用いる．
+    #[derive(Clone, Debug, PartialEq)]
+    struct Affine(i64, i64);
+
+    impl Monoid for Affine {
+        fn mempty() -> Self {
+            Affine(1, 0)
+        }
+
+        fn mappend(&self, other: &Self) -> Self {
+            Affine(
+                self.0 * other.0 % 998244353,
+                (self.1 * other.0 + other.1) % 998244353,
+            )
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_sparse_table_max_i64(v in proptest::collection::vec(-100..100i64, 1..100)) {
+            let st = v.iter().cloned().map(Max).collect::<SparseTable<_>>();
+            for i in 0..v.len() {
+                for j in i..=v.len() {
+                    let max = (i..j).map(|k| v[k]).max().unwrap_or(i64::MIN);
+                    assert_eq!(st.query(i, j).0, max);
+                }
+            }
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_sparse_table_min_u64(v in proptest::collection::vec(0..100u64, 1..100)) {
+            let st = v.iter().cloned().map(Min).collect::<SparseTable<_>>();
+            for i in 0..v.len() {
+                for j in i..=v.len() {
+                    let min = (i..j).map(|k| v[k]).min().unwrap_or(u64::MAX);
+                    assert_eq!(st.query(i, j).0, min);
+                }
+            }
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_disjoint_sparse_table_min_i64(v in proptest::collection::vec(-100..100i64, 1..100)) {
+            let st = v.iter().cloned().map(Min).collect::<DisjointSparseTable<_>>();
+            for i in 0..v.len() {
+                for j in i..=v.len() {
+                    let min = (i..j).map(|k| v[k]).min().unwrap_or(i64::MAX);
+                    assert_eq!(st.query(i, j).0, min);
+                }
+            }
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_disjoint_sparse_table_affine(
+            v in proptest::collection::vec((0..100i64, 0..100i64), 1..100)
+        ) {
+            let st = v.iter().map(|&(a, b)| Affine(a, b)).collect::<DisjointSparseTable<_>>();
+            for i in 0..v.len() {
+                for j in i..=v.len() {
+                    let expected = (i..j).fold(Affine::mempty(), |acc, k| {
+                        acc.mappend(&Affine(v[k].0, v[k].1))
+                    });
+                    assert_eq!(st.query(i, j), expected);
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use std::mem;
+use std::ops::{Add, Sub};
 
 #[derive(Clone, Debug)]
 pub struct UnionFind {
     root: Vec<usize>,
     size: Vec<usize>,
+    n_components: usize,
 }
 
 impl UnionFind {
@@ -11,7 +13,8 @@ impl UnionFind {
     pub fn new(size: usize) -> Self {
         Self {
             root: (0..size).collect(),
-            size: vec![0; size],
+            size: vec![1; size],
+            n_components: size,
         }
     }
 
@@ -39,23 +42,295 @@ impl UnionFind {
         if x_root == y_root {
             return false;
         }
-        if self.size(x_root) < self.size(y_root) {
+        if self.size[x_root] < self.size[y_root] {
             mem::swap(&mut x_root, &mut y_root);
         }
         self.root[y_root] = x_root;
         self.size[x_root] += self.size[y_root];
+        self.n_components -= 1;
         true
     }
 
+    /// 指定した要素が属する木の大きさを返す．
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.root(x);
+        self.size[root]
+    }
+
+    /// 連結成分の個数を返す．
+    pub fn count_components(&self) -> usize {
+        self.n_components
+    }
+
+    /// 各連結成分の根を昇順に返す．
+    pub fn leaders(&self) -> Vec<usize> {
+        (0..self.root.len())
+            .filter(|&x| self.root[x] == x)
+            .collect()
+    }
+
+    /// 連結成分ごとに要素を昇順に並べたものを返す．
+    /// 連結成分は最小の要素の昇順に並ぶ．
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let n = self.root.len();
+        let mut index = vec![usize::MAX; n];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.n_components);
+        for x in 0..n {
+            let root = self.root(x);
+            if index[root] == usize::MAX {
+                index[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[index[root]].push(x);
+        }
+        groups
+    }
+}
+
+/// 要素間のポテンシャルの差を管理する Union Find Tree．
+/// `G` はアーベル群をなす型 (整数型や `ModInt` など)．
+#[derive(Clone, Debug)]
+pub struct WeightedUnionFind<G> {
+    root: Vec<usize>,
+    size: Vec<usize>,
+    // i番目の要素は `potential(i) - potential(root[i])`.
+    weight: Vec<G>,
+}
+
+impl<G> WeightedUnionFind<G>
+where
+    G: Copy + Add<Output = G> + Sub<Output = G> + num::Zero + PartialEq,
+{
+    /// Weighted Union Find Tree を初期化する. `size` は要素数.
+    pub fn new(size: usize) -> Self {
+        Self {
+            root: (0..size).collect(),
+            size: vec![1; size],
+            weight: vec![G::zero(); size],
+        }
+    }
+
+    /// 指定した要素の根を返す(経路圧縮もする)．
+    pub fn root(&mut self, x: usize) -> usize {
+        if self.root[x] == x {
+            x
+        } else {
+            let parent = self.root[x];
+            let root = self.root(parent);
+            self.weight[x] = self.weight[x] + self.weight[parent];
+            self.root[x] = root;
+            root
+        }
+    }
+
+    /// 二つの要素が同じ木にあるかを返す．
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.root(x) == self.root(y)
+    }
+
+    /// `potential(y) - potential(x) = w` という制約を加えて二つの要素が含まれる木を併合する．
+    /// 新たに併合したら `Ok(true)`，既に同じ木にあり制約と矛盾しなければ `Ok(false)` を返す．
+    /// 既存の制約と矛盾する場合は，既存の制約から定まる `potential(y) - potential(x)` を `Err` で返す．
+    pub fn unite(&mut self, x: usize, y: usize, w: G) -> Result<bool, G> {
+        let mut x_root = self.root(x);
+        let mut y_root = self.root(y);
+        if x_root == y_root {
+            let diff = self.weight[y] - self.weight[x];
+            return if diff == w { Ok(false) } else { Err(diff) };
+        }
+        // potential(y_root) - potential(x_root)
+        let mut w = w + self.weight[x] - self.weight[y];
+        if self.size[x_root] < self.size[y_root] {
+            mem::swap(&mut x_root, &mut y_root);
+            w = G::zero() - w;
+        }
+        self.root[y_root] = x_root;
+        self.size[x_root] += self.size[y_root];
+        self.weight[y_root] = w;
+        Ok(true)
+    }
+
+    /// 二つの要素が同じ木にあれば `potential(y) - potential(x)` を返す．
+    pub fn diff(&mut self, x: usize, y: usize) -> Option<G> {
+        if self.same(x, y) {
+            Some(self.weight[y] - self.weight[x])
+        } else {
+            None
+        }
+    }
+
+    /// 指定した要素が属する木の大きさを返す．
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.root(x);
+        self.size[root]
+    }
+}
+
+/// 経路圧縮をせず union by size のみを行うことで，操作を巻き戻せるようにした Union Find Tree．
+/// 各操作は `O(log n)`．
+#[derive(Clone, Debug)]
+pub struct RollbackUnionFind {
+    root: Vec<usize>,
+    size: Vec<usize>,
+    n_components: usize,
+    // `unite` のたびに，併合したなら `Some((親になった根, 子になった根))` を積む．
+    history: Vec<Option<(usize, usize)>>,
+}
+
+impl RollbackUnionFind {
+    /// Rollback Union Find Tree を初期化する. `size` は要素数.
+    pub fn new(size: usize) -> Self {
+        Self {
+            root: (0..size).collect(),
+            size: vec![1; size],
+            n_components: size,
+            history: vec![],
+        }
+    }
+
+    /// 指定した要素の根を返す．
+    pub fn root(&self, mut x: usize) -> usize {
+        while self.root[x] != x {
+            x = self.root[x];
+        }
+        x
+    }
+
+    /// 二つの要素が同じ木にあるかを返す．
+    pub fn same(&self, x: usize, y: usize) -> bool {
+        self.root(x) == self.root(y)
+    }
+
+    /// 二つの要素が含まれる木を併合する．
+    /// 併合しなかった場合も `undo` で取り消す一回の操作として記録する．
+    pub fn unite(&mut self, x: usize, y: usize) -> bool {
+        let mut x_root = self.root(x);
+        let mut y_root = self.root(y);
+        if x_root == y_root {
+            self.history.push(None);
+            return false;
+        }
+        if self.size[x_root] < self.size[y_root] {
+            mem::swap(&mut x_root, &mut y_root);
+        }
+        self.root[y_root] = x_root;
+        self.size[x_root] += self.size[y_root];
+        self.n_components -= 1;
+        self.history.push(Some((x_root, y_root)));
+        true
+    }
+
+    /// 直前の `unite` を取り消す．取り消す操作がなければ `false` を返す．
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            None => false,
+            Some(None) => true,
+            Some(Some((x_root, y_root))) => {
+                self.root[y_root] = y_root;
+                self.size[x_root] -= self.size[y_root];
+                self.n_components += 1;
+                true
+            }
+        }
+    }
+
+    /// 現在の状態を表す値を返す．`rollback` に渡すとこの状態に戻せる．
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// `snapshot` で得た状態まで操作を巻き戻す．
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+
     /// 指定した要素が属する木の大きさを返す．
     pub fn size(&self, x: usize) -> usize {
-        self.size[x]
+        self.size[self.root(x)]
+    }
+
+    /// 連結成分の個数を返す．
+    pub fn count_components(&self) -> usize {
+        self.n_components
+    }
+}
+
+/// 過去の任意の時点の状態を問い合わせられる部分永続 Union Find Tree．
+/// `t` 回目の `unite` の直後の状態を時刻 `t` の状態とし，初期状態は時刻 0 とする．
+/// 各操作は `O(log n)`．
+#[derive(Clone, Debug)]
+pub struct PersistentUnionFind {
+    root: Vec<usize>,
+    // i番目の要素は，i番目の要素が根でなくなった時刻．根であれば `usize::MAX`．
+    time: Vec<usize>,
+    // i番目の要素は，i番目の要素を根とする木の (時刻, 大きさ) の履歴．
+    size: Vec<Vec<(usize, usize)>>,
+    now: usize,
+}
+
+impl PersistentUnionFind {
+    /// Persistent Union Find Tree を初期化する. `size` は要素数.
+    pub fn new(size: usize) -> Self {
+        Self {
+            root: (0..size).collect(),
+            time: vec![usize::MAX; size],
+            size: vec![vec![(0, 1)]; size],
+            now: 0,
+        }
+    }
+
+    /// これまでに `unite` を呼んだ回数 (現在の時刻) を返す．
+    pub fn now(&self) -> usize {
+        self.now
+    }
+
+    /// 時刻 `t` における指定した要素の根を返す．
+    pub fn root(&self, mut x: usize, t: usize) -> usize {
+        while self.time[x] <= t {
+            x = self.root[x];
+        }
+        x
+    }
+
+    /// 時刻 `t` において二つの要素が同じ木にあったかを返す．
+    pub fn same(&self, x: usize, y: usize, t: usize) -> bool {
+        self.root(x, t) == self.root(y, t)
+    }
+
+    /// 二つの要素が含まれる木を併合し，時刻を一つ進める．
+    /// 併合しなかった場合も時刻は進む．
+    pub fn unite(&mut self, x: usize, y: usize) -> bool {
+        self.now += 1;
+        let mut x_root = self.root(x, self.now);
+        let mut y_root = self.root(y, self.now);
+        if x_root == y_root {
+            return false;
+        }
+        let x_size = self.size[x_root].last().unwrap().1;
+        let y_size = self.size[y_root].last().unwrap().1;
+        if x_size < y_size {
+            mem::swap(&mut x_root, &mut y_root);
+        }
+        self.root[y_root] = x_root;
+        self.time[y_root] = self.now;
+        self.size[x_root].push((self.now, x_size + y_size));
+        true
+    }
+
+    /// 時刻 `t` において指定した要素が属していた木の大きさを返す．
+    pub fn size(&self, x: usize, t: usize) -> usize {
+        let history = &self.size[self.root(x, t)];
+        let i = history.partition_point(|&(time, _)| time <= t);
+        history[i - 1].1
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::data_structure::unionfind;
+    use proptest::strategy::{Just, Strategy};
 
     #[test]
     fn random_unite() {
@@ -78,4 +353,226 @@ mod tests {
         assert!(!uf.same(0, 1));
         assert!(!uf.same(0, 4));
     }
+
+    #[test]
+    fn size_and_groups() {
+        let mut uf = unionfind::UnionFind::new(5);
+        assert_eq!(uf.size(0), 1);
+        assert_eq!(uf.count_components(), 5);
+
+        uf.unite(1, 2);
+        uf.unite(3, 2);
+        assert_eq!(uf.size(1), 3);
+        assert_eq!(uf.size(3), 3);
+        assert_eq!(uf.size(4), 1);
+        assert_eq!(uf.count_components(), 3);
+        assert_eq!(uf.leaders().len(), 3);
+        assert_eq!(uf.groups(), vec![vec![0], vec![1, 2, 3], vec![4]]);
+    }
+
+    /// 辺集合から BFS で各頂点の連結成分の番号を求める．
+    fn naive_components(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+        let mut graph = vec![vec![]; n];
+        for &(x, y) in edges {
+            graph[x].push(y);
+            graph[y].push(x);
+        }
+        let mut component = vec![usize::MAX; n];
+        for start in 0..n {
+            if component[start] != usize::MAX {
+                continue;
+            }
+            component[start] = start;
+            let mut queue = std::collections::VecDeque::from(vec![start]);
+            while let Some(v) = queue.pop_front() {
+                for &next in &graph[v] {
+                    if component[next] == usize::MAX {
+                        component[next] = start;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        component
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_unionfind_random(
+            (n, edges) in (1..50usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n), 0..50))
+            })
+        ) {
+            let mut uf = unionfind::UnionFind::new(n);
+            for &(x, y) in &edges {
+                uf.unite(x, y);
+            }
+            let component = naive_components(n, &edges);
+            for x in 0..n {
+                for y in 0..n {
+                    assert_eq!(uf.same(x, y), component[x] == component[y]);
+                }
+                let size = (0..n).filter(|&y| component[x] == component[y]).count();
+                assert_eq!(uf.size(x), size);
+            }
+            let mut leaders = component.clone();
+            leaders.sort();
+            leaders.dedup();
+            assert_eq!(uf.count_components(), leaders.len());
+            assert_eq!(uf.leaders().len(), leaders.len());
+            let groups = uf.groups();
+            assert_eq!(groups.len(), leaders.len());
+            for group in groups {
+                assert!(group.iter().all(|&x| component[x] == component[group[0]]));
+                assert_eq!(group.len(), uf.size(group[0]));
+            }
+        }
+    }
+
+    #[test]
+    fn weighted_unite_contradiction() {
+        let mut uf = unionfind::WeightedUnionFind::<i64>::new(3);
+        assert_eq!(uf.unite(0, 1, 3), Ok(true));
+        assert_eq!(uf.unite(1, 2, -5), Ok(true));
+        assert_eq!(uf.diff(0, 2), Some(-2));
+        assert_eq!(uf.diff(2, 0), Some(2));
+        assert_eq!(uf.unite(0, 2, -2), Ok(false));
+        assert_eq!(uf.unite(0, 2, 4), Err(-2));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_weighted_unionfind_random(
+            (potential, edges) in (1..50usize).prop_flat_map(|n| {
+                (
+                    proptest::collection::vec(-100..100i64, n),
+                    proptest::collection::vec((0..n, 0..n, 0..5i64), 0..50),
+                )
+            })
+        ) {
+            let n = potential.len();
+            let mut uf = unionfind::WeightedUnionFind::new(n);
+            // 一部の制約にはずれを加え，矛盾が検出されることを確かめる．
+            for &(x, y, noise) in &edges {
+                let w = potential[y] - potential[x] + noise;
+                let expected = match uf.diff(x, y) {
+                    None => Ok(true),
+                    Some(d) if d == w => Ok(false),
+                    Some(d) => Err(d),
+                };
+                assert_eq!(uf.unite(x, y, w), expected);
+            }
+            let mut uf = unionfind::WeightedUnionFind::new(n);
+            for &(x, y, _) in &edges {
+                assert!(uf.unite(x, y, potential[y] - potential[x]).is_ok());
+            }
+            let mut plain = unionfind::UnionFind::new(n);
+            for &(x, y, _) in &edges {
+                plain.unite(x, y);
+            }
+            for x in 0..n {
+                for y in 0..n {
+                    let expected = if plain.same(x, y) {
+                        Some(potential[y] - potential[x])
+                    } else {
+                        None
+                    };
+                    assert_eq!(uf.diff(x, y), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rollback_unite() {
+        let mut uf = unionfind::RollbackUnionFind::new(4);
+        uf.unite(0, 1);
+        let snapshot = uf.snapshot();
+        uf.unite(1, 2);
+        uf.unite(0, 2);
+        uf.unite(2, 3);
+        assert!(uf.same(0, 3));
+        assert_eq!(uf.size(0), 4);
+        assert_eq!(uf.count_components(), 1);
+
+        uf.rollback(snapshot);
+        assert!(uf.same(0, 1));
+        assert!(!uf.same(1, 2));
+        assert!(!uf.same(2, 3));
+        assert_eq!(uf.size(1), 2);
+        assert_eq!(uf.count_components(), 3);
+
+        assert!(uf.undo());
+        assert!(!uf.same(0, 1));
+        assert!(!uf.undo());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_rollback_unionfind_random(
+            (n, edges, keep) in (1..30usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n), 0..30), 0..30usize)
+            })
+        ) {
+            let keep = keep.min(edges.len());
+            let mut uf = unionfind::RollbackUnionFind::new(n);
+            for &(x, y) in &edges[..keep] {
+                uf.unite(x, y);
+            }
+            let snapshot = uf.snapshot();
+            for &(x, y) in &edges[keep..] {
+                uf.unite(x, y);
+            }
+            uf.rollback(snapshot);
+            let component = naive_components(n, &edges[..keep]);
+            for x in 0..n {
+                for y in 0..n {
+                    assert_eq!(uf.same(x, y), component[x] == component[y]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn persistent_unite() {
+        let mut uf = unionfind::PersistentUnionFind::new(4);
+        uf.unite(0, 1);
+        uf.unite(2, 3);
+        uf.unite(1, 0);
+        uf.unite(1, 3);
+        assert_eq!(uf.now(), 4);
+        assert!(!uf.same(0, 1, 0));
+        assert!(uf.same(0, 1, 1));
+        assert!(!uf.same(0, 3, 3));
+        assert!(uf.same(0, 3, 4));
+        assert_eq!(uf.size(2, 1), 1);
+        assert_eq!(uf.size(2, 2), 2);
+        assert_eq!(uf.size(2, 4), 4);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_persistent_unionfind_random(
+            (n, edges) in (1..20usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n), 0..30))
+            })
+        ) {
+            let mut uf = unionfind::PersistentUnionFind::new(n);
+            for &(x, y) in &edges {
+                uf.unite(x, y);
+            }
+            for t in 0..=edges.len() {
+                let mut expected = unionfind::UnionFind::new(n);
+                for &(x, y) in &edges[..t] {
+                    expected.unite(x, y);
+                }
+                for x in 0..n {
+                    for y in 0..n {
+                        assert_eq!(uf.same(x, y, t), expected.same(x, y));
+                    }
+                    assert_eq!(uf.size(x, t), expected.size(x));
+                }
+            }
+        }
+    }
 }
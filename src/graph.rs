@@ -1,9 +1,256 @@
+pub mod all_pairs;
+pub mod astar;
+pub mod bellman_ford;
 pub mod dijkstra;
+pub mod grid;
+pub mod k_shortest_paths;
+pub mod lowlink;
+pub mod mst;
+pub mod scc;
+pub mod toposort;
+pub mod tree;
+pub mod two_sat;
+
+pub use bellman_ford::bellman_ford;
+pub use k_shortest_paths::{k_shortest_paths, yen};
+pub use scc::scc;
+pub use toposort::toposort;
+
+use proconio::marker::Usize1;
+use proconio::source::{Readable, Source};
+use std::io::BufRead;
+use std::ops::Index;
+
+/// グラフの辺．重みなしグラフでは `weight` は `()` になる．
+/// `id` は辺を追加した順に 0 から振られる番号で，無向グラフでは両方向の辺が同じ `id` を持つ．
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Edge<W> {
+    pub from: usize,
+    pub to: usize,
+    pub weight: W,
+    pub id: usize,
+}
+
+/// `Graph::from_edges` に渡せる辺の表現．
+/// 重みなしの辺は `(from, to)`，重みつきの辺は `(from, to, weight)` で表す．
+pub trait IntoEdge<W> {
+    fn into_edge(self) -> (usize, usize, W);
+}
+
+impl IntoEdge<()> for (usize, usize) {
+    fn into_edge(self) -> (usize, usize, ()) {
+        (self.0, self.1, ())
+    }
+}
+
+impl<W> IntoEdge<W> for (usize, usize, W) {
+    fn into_edge(self) -> (usize, usize, W) {
+        self
+    }
+}
+
+/// 隣接リストで表現されたグラフ．節点は 0-indexed．
+/// `graph[v]` で節点 `v` から出る辺のスライスが得られる．
+#[derive(Clone, Debug)]
+pub struct Graph<W> {
+    adjacency: Vec<Vec<Edge<W>>>,
+    // 追加された順の辺．無向グラフでも一本の辺は一度だけ現れる．
+    edges: Vec<Edge<W>>,
+    directed: bool,
+}
+
+impl<W> Graph<W>
+where
+    W: Clone,
+{
+    /// 節点数 `size` の辺のない有向グラフを作る．
+    pub fn directed(size: usize) -> Self {
+        Self {
+            adjacency: vec![vec![]; size],
+            edges: vec![],
+            directed: true,
+        }
+    }
+
+    /// 節点数 `size` の辺のない無向グラフを作る．
+    pub fn undirected(size: usize) -> Self {
+        Self {
+            directed: false,
+            ..Self::directed(size)
+        }
+    }
+
+    /// 節点数 `size` で，`edges` の辺を持つグラフを作る．
+    pub fn from_edges<I, E>(size: usize, directed: bool, edges: I) -> Self
+    where
+        I: IntoIterator<Item = E>,
+        E: IntoEdge<W>,
+    {
+        let mut graph = if directed {
+            Self::directed(size)
+        } else {
+            Self::undirected(size)
+        };
+        for edge in edges {
+            let (from, to, weight) = edge.into_edge();
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+
+    /// `from` 番目の節点から `to` 番目の節点へ重み `weight` の辺を張り，その辺の `id` を返す．
+    /// 無向グラフなら逆向きの辺も張る．
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) -> usize {
+        let id = self.edges.len();
+        let edge = Edge {
+            from,
+            to,
+            weight,
+            id,
+        };
+        if !self.directed {
+            self.adjacency[to].push(Edge {
+                from: to,
+                to: from,
+                ..edge.clone()
+            });
+        }
+        self.adjacency[from].push(edge.clone());
+        self.edges.push(edge);
+        id
+    }
+
+    /// すべての辺の向きを逆にしたグラフを返す．無向グラフならそのまま複製する．
+    pub fn reversed(&self) -> Self {
+        let mut graph = Self {
+            adjacency: vec![vec![]; self.len()],
+            edges: vec![],
+            directed: self.directed,
+        };
+        for edge in &self.edges {
+            graph.add_edge(edge.to, edge.from, edge.weight.clone());
+        }
+        graph
+    }
+
+    /// 辺を節点ごとに一つの配列へ詰めた CSR 形式に変換する．
+    pub fn to_csr(&self) -> Csr<W> {
+        let mut start = Vec::with_capacity(self.len() + 1);
+        start.push(0);
+        for edges in &self.adjacency {
+            start.push(start.last().unwrap() + edges.len());
+        }
+        let edges = self.adjacency.iter().flatten().cloned().collect();
+        Csr { start, edges }
+    }
+}
+
+impl<W> Graph<W> {
+    /// 節点数を返す．
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// 節点を持たないかどうかを返す．
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    /// 辺の本数を返す．無向グラフでは両方向の辺をまとめて一本と数える．
+    pub fn n_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// 有向グラフかどうかを返す．
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// `id` 番目に追加した辺を返す．
+    pub fn edge(&self, id: usize) -> &Edge<W> {
+        &self.edges[id]
+    }
+
+    /// 追加した順にすべての辺を返す．無向グラフでも一本の辺は一度だけ現れる．
+    pub fn edges(&self) -> &[Edge<W>] {
+        &self.edges
+    }
+}
+
+impl<W> Index<usize> for Graph<W> {
+    type Output = [Edge<W>];
+
+    fn index(&self, v: usize) -> &Self::Output {
+        &self.adjacency[v]
+    }
+}
+
+/// CSR (Compressed Sparse Row) 形式で表現されたグラフ．構築後は辺を追加できない．
+/// `Graph` より省メモリで，辺を走査するときのキャッシュ効率がよい．
+#[derive(Clone, Debug)]
+pub struct Csr<W> {
+    // 節点 `v` から出る辺は `edges[start[v]..start[v + 1]]`.
+    start: Vec<usize>,
+    edges: Vec<Edge<W>>,
+}
+
+impl<W> Csr<W> {
+    /// 節点数を返す．
+    pub fn len(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    /// 節点を持たないかどうかを返す．
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<W> Index<usize> for Csr<W> {
+    type Output = [Edge<W>];
+
+    fn index(&self, v: usize) -> &Self::Output {
+        &self.edges[self.start[v]..self.start[v + 1]]
+    }
+}
+
+/// "N M" の後に M 行の "u v" が続く形式の重みなしグラフを読み込む．
+/// 入力の節点番号は 1-indexed とし，0-indexed に変換する．
+pub fn read_graph<R, S>(source: &mut S, directed: bool) -> Graph<()>
+where
+    R: BufRead,
+    S: Source<R>,
+{
+    proconio::input! {
+        from &mut *source,
+        n: usize,
+        m: usize,
+        edges: [(Usize1, Usize1); m],
+    }
+    Graph::from_edges(n, directed, edges)
+}
+
+/// "N M" の後に M 行の "u v w" が続く形式の重みつきグラフを読み込む．
+/// 入力の節点番号は 1-indexed とし，0-indexed に変換する．
+pub fn read_weighted_graph<W, R, S>(source: &mut S, directed: bool) -> Graph<W>
+where
+    W: Readable<Output = W> + Clone,
+    R: BufRead,
+    S: Source<R>,
+{
+    proconio::input! {
+        from &mut *source,
+        n: usize,
+        m: usize,
+        edges: [(Usize1, Usize1, W); m],
+    }
+    Graph::from_edges(n, directed, edges)
+}
 
 /// 二次元配列で表現されたグリッドにおいて,ある点に隣接する4方向の節点を返す.
 /// グリッドからはみ出るような節点は無視される.
 pub fn adjacent4<T>(
-    graph: &Vec<Vec<T>>,
+    graph: &[Vec<T>],
     row: usize,
     col: usize,
 ) -> impl Iterator<Item = (usize, usize)> {
@@ -22,9 +269,41 @@ pub fn adjacent4<T>(
         })
 }
 
+/// 二次元配列で表現されたグリッドにおいて,ある点に隣接する8方向の節点を返す.
+/// グリッドからはみ出るような節点は無視される.
+pub fn adjacent8<T>(
+    graph: &[Vec<T>],
+    row: usize,
+    col: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    let height = graph.len() as isize;
+    let width = graph[0].len() as isize;
+    [
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+        (0, -1),
+        (1, -1),
+    ]
+    .iter()
+    .filter_map(move |&(dx, dy)| {
+        let nx = row as isize + dx;
+        let ny = col as isize + dy;
+        if nx >= 0 && nx < height && ny >= 0 && ny < width {
+            Some((nx as usize, ny as usize))
+        } else {
+            None
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::graph::adjacent4;
+    use crate::graph::{adjacent4, adjacent8, read_graph, read_weighted_graph, Edge, Graph};
+    use proconio::source::once::OnceSource;
 
     #[test]
     fn test_adjacent4_corner() {
@@ -49,4 +328,78 @@ mod tests {
         let actual = adjacent4(&graph, 1, 1).collect::<Vec<(usize, usize)>>();
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_adjacent8_corner() {
+        let graph = vec![vec![0; 3]; 3];
+        let expected = vec![(1, 0), (1, 1), (0, 1)];
+        let actual = adjacent8(&graph, 0, 0).collect::<Vec<(usize, usize)>>();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_adjacent8_center() {
+        let graph = vec![vec![0; 3]; 3];
+        let expected = vec![
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+            (0, 0),
+            (1, 0),
+            (2, 0),
+        ];
+        let actual = adjacent8(&graph, 1, 1).collect::<Vec<(usize, usize)>>();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_undirected_graph_has_both_directions() {
+        let graph = Graph::from_edges(3, false, vec![(0, 1, 5), (1, 2, 7)]);
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.n_edges(), 2);
+        assert_eq!(graph[1].len(), 2);
+        assert_eq!(
+            graph[1][0],
+            Edge {
+                from: 1,
+                to: 0,
+                weight: 5,
+                id: 0
+            }
+        );
+        assert_eq!(graph.edge(1).to, 2);
+    }
+
+    #[test]
+    fn test_directed_graph_and_csr() {
+        let graph = Graph::from_edges(4, true, vec![(0, 1), (0, 2), (2, 3), (3, 0)]);
+        let csr = graph.to_csr();
+        assert_eq!(csr.len(), 4);
+        for v in 0..4 {
+            assert_eq!(&graph[v], &csr[v]);
+        }
+        let reversed = graph.reversed();
+        assert_eq!(
+            reversed[0].iter().map(|e| e.to).collect::<Vec<_>>(),
+            vec![3]
+        );
+    }
+
+    #[test]
+    fn test_read_graph() {
+        let mut source = OnceSource::from("3 2\n1 2\n2 3\n");
+        let graph = read_graph(&mut source, true);
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph[0][0].to, 1);
+        assert_eq!(graph[1][0].to, 2);
+        assert!(graph[2].is_empty());
+
+        let mut source = OnceSource::from("2 1\n2 1 10\n");
+        let graph = read_weighted_graph::<i64, _, _>(&mut source, false);
+        assert_eq!(graph[0][0].to, 1);
+        assert_eq!(graph[0][0].weight, 10);
+        assert_eq!(graph[1][0].to, 0);
+    }
 }
@@ -0,0 +1,246 @@
+use crate::graph::bellman_ford::NEG_INF;
+use crate::graph::dijkstra::Dijkstra;
+use crate::graph::Graph;
+
+/// Floyd-Warshall 法で全点対間の最短経路を求めるための構造体．
+/// 隣接行列で辺を持つので密なグラフに向く．`O(n^3)`．
+pub struct FloydWarshall {
+    // `distance[i][j]` はi番目の節点からj番目の節点への最短距離. 到達できなければ `None`.
+    distance: Vec<Vec<Option<i64>>>,
+    // `next[i][j]` はi番目の節点からj番目の節点への最短経路で，i番目の次に通る節点.
+    next: Vec<Vec<usize>>,
+}
+
+impl FloydWarshall {
+    /// Floyd-Warshall 法で最短経路を求めるための構造体を初期化する．
+    /// `size` は節点数．
+    pub fn new(size: usize) -> Self {
+        let mut distance = vec![vec![None; size]; size];
+        for (i, row) in distance.iter_mut().enumerate() {
+            row[i] = Some(0);
+        }
+        let next = (0..size).map(|_| (0..size).collect()).collect();
+        Self { distance, next }
+    }
+
+    /// `from` 番目の節点から `to` 番目の節点へコスト `cost` の辺を張る．
+    /// 多重辺はコストの最も小さいものだけが残る．
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: i64) {
+        if self.distance[from][to].is_none_or(|d| d > cost) {
+            self.distance[from][to] = Some(cost);
+        }
+    }
+
+    /// 全点対間の最短経路を求める．
+    pub fn solve(&mut self) {
+        let n = self.distance.len();
+        for k in 0..n {
+            for i in 0..n {
+                let d_ik = match self.distance[i][k] {
+                    Some(d) => d,
+                    None => continue,
+                };
+                for j in 0..n {
+                    if let Some(d_kj) = self.distance[k][j] {
+                        // 負閉路があると距離が際限なく小さくなるので，NEG_INF で飽和させる．
+                        let candidate = d_ik.saturating_add(d_kj);
+                        if self.distance[i][j].is_none_or(|d| d > candidate) {
+                            self.distance[i][j] = Some(candidate);
+                            self.next[i][j] = self.next[i][k];
+                        }
+                    }
+                }
+            }
+        }
+        // 負閉路上の節点を経由できる節点対の距離は NEG_INF にする．
+        for k in 0..n {
+            if self.distance[k][k].is_none_or(|d| d >= 0) {
+                continue;
+            }
+            for i in 0..n {
+                if self.distance[i][k].is_none() {
+                    continue;
+                }
+                for j in 0..n {
+                    if self.distance[k][j].is_some() {
+                        self.distance[i][j] = Some(NEG_INF);
+                    }
+                }
+            }
+        }
+    }
+
+    /// グラフが負閉路を含むかどうかを返す．
+    pub fn has_negative_cycle(&self) -> bool {
+        (0..self.distance.len()).any(|i| self.distance[i][i].is_some_and(|d| d < 0))
+    }
+
+    /// `from` 番目の節点から `to` 番目の節点への距離を返す．
+    /// 到達できなければ `None`，負閉路を経由できるなら `Some(NEG_INF)` を返す．
+    pub fn distance(&self, from: usize, to: usize) -> Option<i64> {
+        self.distance[from][to]
+    }
+
+    /// `from` 番目の節点から `to` 番目の節点への最短経路を，通る節点を順に並べて返す．
+    /// 到達できないか，負閉路を経由できて最短経路が存在しなければ `None` を返す．
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        if self.distance[from][to].is_none_or(|d| d == NEG_INF) {
+            return None;
+        }
+        let mut path = vec![from];
+        let mut current = from;
+        while current != to {
+            current = self.next[current][to];
+            path.push(current);
+        }
+        Some(path)
+    }
+}
+
+impl From<&Graph<i64>> for FloydWarshall {
+    fn from(graph: &Graph<i64>) -> Self {
+        let mut floyd_warshall = FloydWarshall::new(graph.len());
+        for v in 0..graph.len() {
+            for edge in &graph[v] {
+                floyd_warshall.add_edge(v, edge.to, edge.weight);
+            }
+        }
+        floyd_warshall
+    }
+}
+
+/// Johnson 法で全点対間の最短距離を求める．`O(nm log n)`．
+/// Bellman-Ford 法で求めたポテンシャルで辺の重みを非負にしてから，各節点を始点として
+/// `Dijkstra` を解く．負の辺を含む疎なグラフで Floyd-Warshall 法より速い．
+/// 負閉路があれば `None` を返す．返り値の `[i][j]` はi番目からj番目への距離で，到達できなければ `None`．
+pub fn johnson(graph: &Graph<i64>) -> Option<Vec<Vec<Option<i64>>>> {
+    let n = graph.len();
+    let potential = potential(graph)?;
+    let mut reweighted = Graph::directed(n);
+    for v in 0..n {
+        for edge in &graph[v] {
+            reweighted.add_edge(v, edge.to, edge.weight + potential[v] - potential[edge.to]);
+        }
+    }
+
+    let mut dijkstra = Dijkstra::from(reweighted);
+    let distance = (0..n)
+        .map(|from| {
+            dijkstra.solve(from);
+            (0..n)
+                .map(|to| {
+                    dijkstra
+                        .distance(to)
+                        .map(|d| d - potential[from] + potential[to])
+                })
+                .collect()
+        })
+        .collect();
+    Some(distance)
+}
+
+/// すべての辺 `(u, v, w)` について `w + p[u] - p[v] >= 0` となるポテンシャル `p` を求める．
+/// 負閉路があれば `None` を返す．
+fn potential(graph: &Graph<i64>) -> Option<Vec<i64>> {
+    let n = graph.len();
+    let mut potential = vec![0i64; n];
+    for _ in 0..=n {
+        let mut updated = false;
+        for v in 0..n {
+            for edge in &graph[v] {
+                let candidate = potential[v] + edge.weight;
+                if candidate < potential[edge.to] {
+                    potential[edge.to] = candidate;
+                    updated = true;
+                }
+            }
+        }
+        if !updated {
+            return Some(potential);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::bellman_ford::bellman_ford;
+    use proptest::strategy::{Just, Strategy};
+
+    #[test]
+    fn test_floyd_warshall_path() {
+        let mut floyd_warshall = FloydWarshall::new(4);
+        floyd_warshall.add_edge(0, 1, 5);
+        floyd_warshall.add_edge(0, 2, 1);
+        floyd_warshall.add_edge(2, 1, 1);
+        floyd_warshall.add_edge(1, 3, -1);
+        floyd_warshall.solve();
+        assert!(!floyd_warshall.has_negative_cycle());
+        assert_eq!(floyd_warshall.distance(0, 3), Some(1));
+        assert_eq!(floyd_warshall.distance(3, 0), None);
+        assert_eq!(floyd_warshall.path(0, 3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(floyd_warshall.path(2, 2), Some(vec![2]));
+        assert_eq!(floyd_warshall.path(3, 0), None);
+    }
+
+    #[test]
+    fn test_floyd_warshall_negative_cycle() {
+        let graph = Graph::from_edges(4, true, vec![(0, 1, 1), (1, 2, -2), (2, 1, 1), (3, 0, 1)]);
+        let mut floyd_warshall = FloydWarshall::from(&graph);
+        floyd_warshall.solve();
+        assert!(floyd_warshall.has_negative_cycle());
+        assert_eq!(floyd_warshall.distance(0, 2), Some(NEG_INF));
+        assert_eq!(floyd_warshall.distance(2, 3), None);
+        assert_eq!(floyd_warshall.path(0, 2), None);
+        assert_eq!(johnson(&graph), None);
+    }
+
+    #[test]
+    fn test_floyd_warshall_large_negative_cycle_does_not_overflow() {
+        // 辺の重みがすべて -1 の完全グラフでは，緩和のたびに距離がおよそ倍になる．
+        let n = 80;
+        let edges = (0..n).flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j, -1)));
+        let graph = Graph::from_edges(n, true, edges);
+        let mut floyd_warshall = FloydWarshall::from(&graph);
+        floyd_warshall.solve();
+        assert!(floyd_warshall.has_negative_cycle());
+        for from in 0..n {
+            for to in 0..n {
+                assert_eq!(floyd_warshall.distance(from, to), Some(NEG_INF));
+            }
+        }
+        assert_eq!(johnson(&graph), None);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_all_pairs_random(
+            (n, edges) in (1..10usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n, -3..10i64), 0..20))
+            })
+        ) {
+            let graph = Graph::from_edges(n, true, edges);
+            let mut floyd_warshall = FloydWarshall::from(&graph);
+            floyd_warshall.solve();
+            let all_pairs = johnson(&graph);
+            assert_eq!(all_pairs.is_none(), floyd_warshall.has_negative_cycle());
+            for from in 0..n {
+                let expected = bellman_ford(&graph, from);
+                for to in 0..n {
+                    assert_eq!(floyd_warshall.distance(from, to), expected[to]);
+                    if let Some(all_pairs) = &all_pairs {
+                        assert_eq!(all_pairs[from][to], expected[to]);
+                    }
+                    if let Some(path) = floyd_warshall.path(from, to) {
+                        let length = path
+                            .windows(2)
+                            .map(|w| graph[w[0]].iter().filter(|e| e.to == w[1]).map(|e| e.weight).min().unwrap())
+                            .sum::<i64>();
+                        assert_eq!(Some(length), expected[to]);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::graph::dijkstra::Cost;
+use crate::graph::Graph;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// A* 探索で `start` 番目の節点から `goal` 番目の節点への最短距離を求める．
+/// 到達できなければ `None` を返す．
+/// `heuristic(v)` は `v` から `goal` への距離の下界で，各辺 `(u, v, w)` について
+/// `heuristic(u) <= w + heuristic(v)` を満たさなければならない (consistent)．
+/// 常に `C::zero()` を返すなら Dijkstra 法と同じになる．
+pub fn astar<C, H>(graph: &Graph<C>, start: usize, goal: usize, heuristic: H) -> Option<C>
+where
+    C: Cost,
+    H: Fn(usize) -> C,
+{
+    let mut distance: Vec<Option<C>> = vec![None; graph.len()];
+    // (スタートからの距離 + ゴールまでの推定距離, スタートからの距離, 節点) を格納する
+    let mut bin_heap = BinaryHeap::new();
+    distance[start] = Some(C::zero());
+    bin_heap.push(Reverse((heuristic(start), C::zero(), start)));
+    while let Some(Reverse((_, cost, current))) = bin_heap.pop() {
+        if distance[current] < Some(cost) {
+            continue;
+        }
+        if current == goal {
+            return Some(cost);
+        }
+        for next_edge in &graph[current] {
+            let candidate = cost.add_cost(next_edge.weight);
+            if distance[next_edge.to].is_none_or(|d| d > candidate) {
+                distance[next_edge.to] = Some(candidate);
+                let estimate = candidate.add_cost(heuristic(next_edge.to));
+                bin_heap.push(Reverse((estimate, candidate, next_edge.to)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dijkstra::Dijkstra;
+
+    #[test]
+    fn test_astar_on_grid() {
+        // 5x5 のグリッドで，マンハッタン距離をヒューリスティックとする．
+        let (height, width) = (5, 5);
+        let walls = [(1, 1), (1, 2), (1, 3), (3, 1), (3, 2), (3, 3)];
+        let id = |row: usize, col: usize| row * width + col;
+        let mut graph = Graph::directed(height * width);
+        for row in 0..height {
+            for col in 0..width {
+                if walls.contains(&(row, col)) {
+                    continue;
+                }
+                let cells = vec![vec![(); width]; height];
+                for (nr, nc) in crate::graph::adjacent4(&cells, row, col) {
+                    if !walls.contains(&(nr, nc)) {
+                        graph.add_edge(id(row, col), id(nr, nc), 1u32);
+                    }
+                }
+            }
+        }
+        let goal = id(2, 2);
+        let heuristic = |v: usize| ((v / width).abs_diff(2) + (v % width).abs_diff(2)) as u32;
+        let mut dijkstra = Dijkstra::from(graph.clone());
+        for start in 0..height * width {
+            dijkstra.solve(start);
+            assert_eq!(
+                astar(&graph, start, goal, heuristic),
+                dijkstra.distance(goal)
+            );
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::graph::Graph;
+use std::collections::VecDeque;
+
+/// 負閉路を通っていくらでも距離を小さくできる節点の距離として使う値．
+pub const NEG_INF: i64 = i64::MIN;
+
+/// Bellman-Ford 法で `start` 番目の節点から各節点への最短距離を求める．`O(nm)`．
+/// 到達できない節点は `None`，負閉路を経由して到達できる節点は `Some(NEG_INF)` になる．
+pub fn bellman_ford(graph: &Graph<i64>, start: usize) -> Vec<Option<i64>> {
+    let n = graph.len();
+    let mut distance = vec![None; n];
+    distance[start] = Some(0);
+    // n - 1 回の緩和で距離が確定しなければ負閉路の影響を受けている．
+    // さらに n 回繰り返して負閉路から到達できる節点すべてに NEG_INF を伝播させる．
+    for round in 0..2 * n {
+        let mut updated = false;
+        for v in 0..n {
+            let d = match distance[v] {
+                Some(d) => d,
+                None => continue,
+            };
+            for edge in &graph[v] {
+                let candidate = if d == NEG_INF {
+                    NEG_INF
+                } else {
+                    d + edge.weight
+                };
+                if distance[edge.to].is_none_or(|current| current > candidate) {
+                    distance[edge.to] = Some(if round + 1 >= n { NEG_INF } else { candidate });
+                    updated = true;
+                }
+            }
+        }
+        if !updated {
+            break;
+        }
+    }
+    distance
+}
+
+/// キューを用いて Bellman-Ford 法を高速化した SPFA で最短距離を求める．
+/// 返り値は `bellman_ford` と同じ．最悪計算量は `O(nm)` だが，多くの場合より速い．
+pub fn spfa(graph: &Graph<i64>, start: usize) -> Vec<Option<i64>> {
+    let n = graph.len();
+    let mut distance = vec![None; n];
+    // i番目の要素は現在の最短経路に含まれる辺の本数．n 本以上なら負閉路を通っている．
+    let mut n_edges = vec![0; n];
+    let mut in_queue = vec![false; n];
+    let mut negative = vec![false; n];
+    let mut queue = VecDeque::new();
+    distance[start] = Some(0);
+    queue.push_back(start);
+    in_queue[start] = true;
+    while let Some(v) = queue.pop_front() {
+        in_queue[v] = false;
+        if negative[v] {
+            continue;
+        }
+        let d = distance[v].unwrap();
+        for edge in &graph[v] {
+            if negative[edge.to] {
+                continue;
+            }
+            let candidate = d + edge.weight;
+            if distance[edge.to].is_none_or(|current| current > candidate) {
+                distance[edge.to] = Some(candidate);
+                n_edges[edge.to] = n_edges[v] + 1;
+                if n_edges[edge.to] >= n {
+                    negative[edge.to] = true;
+                } else if !in_queue[edge.to] {
+                    queue.push_back(edge.to);
+                    in_queue[edge.to] = true;
+                }
+            }
+        }
+    }
+
+    // 負閉路を通っていることが分かった節点から到達できる節点はすべて NEG_INF になる．
+    let mut queue = (0..n).filter(|&v| negative[v]).collect::<VecDeque<_>>();
+    while let Some(v) = queue.pop_front() {
+        distance[v] = Some(NEG_INF);
+        for edge in &graph[v] {
+            if !negative[edge.to] {
+                negative[edge.to] = true;
+                queue.push_back(edge.to);
+            }
+        }
+    }
+    distance
+}
+
+/// グラフに含まれる負閉路を一つ求め，閉路上の節点を辺をたどる順に返す．
+/// 始点からの到達可能性は問わない．負閉路がなければ `None` を返す．`O(nm)`．
+/// 無向グラフでは負の重みの辺が一本あればそれを往復する閉路が見つかる．
+pub fn negative_cycle(graph: &Graph<i64>) -> Option<Vec<usize>> {
+    let n = graph.len();
+    // すべての節点へ重み 0 の辺を張った仮想的な始点から始めたものとみなす．
+    let mut distance = vec![0i64; n];
+    let mut prev = vec![usize::MAX; n];
+    let mut last_updated = None;
+    for _ in 0..n {
+        last_updated = None;
+        for v in 0..n {
+            for edge in &graph[v] {
+                let candidate = distance[v] + edge.weight;
+                if candidate < distance[edge.to] {
+                    distance[edge.to] = candidate;
+                    prev[edge.to] = v;
+                    last_updated = Some(edge.to);
+                }
+            }
+        }
+        last_updated?;
+    }
+
+    // n 回目にも更新された節点から n 回さかのぼれば必ず負閉路上にいる．
+    let mut v = last_updated?;
+    for _ in 0..n {
+        v = prev[v];
+    }
+    let mut cycle = vec![v];
+    let mut current = prev[v];
+    while current != v {
+        cycle.push(current);
+        current = prev[current];
+    }
+    cycle.reverse();
+    Some(cycle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::{Just, Strategy};
+
+    #[test]
+    fn test_bellman_ford_with_negative_edges() {
+        let graph = Graph::from_edges(4, true, vec![(0, 1, 4), (0, 2, 5), (2, 1, -3), (1, 3, 2)]);
+        let expected = vec![Some(0), Some(2), Some(5), Some(4)];
+        assert_eq!(bellman_ford(&graph, 0), expected);
+        assert_eq!(spfa(&graph, 0), expected);
+        assert_eq!(negative_cycle(&graph), None);
+    }
+
+    #[test]
+    fn test_bellman_ford_with_negative_cycle() {
+        // 1 -> 2 -> 3 -> 1 が負閉路で，4 はそこから到達でき，5 は到達できない．
+        let graph = Graph::from_edges(
+            6,
+            true,
+            vec![
+                (0, 1, 1),
+                (1, 2, 1),
+                (2, 3, -3),
+                (3, 1, 1),
+                (3, 4, 1),
+                (5, 0, 1),
+            ],
+        );
+        let expected = vec![
+            Some(0),
+            Some(NEG_INF),
+            Some(NEG_INF),
+            Some(NEG_INF),
+            Some(NEG_INF),
+            None,
+        ];
+        assert_eq!(bellman_ford(&graph, 0), expected);
+        assert_eq!(spfa(&graph, 0), expected);
+
+        let mut cycle = negative_cycle(&graph).unwrap();
+        let position = cycle.iter().position(|&v| v == 1).unwrap();
+        cycle.rotate_left(position);
+        assert_eq!(cycle, vec![1, 2, 3]);
+    }
+
+    fn is_negative_cycle(graph: &Graph<i64>, cycle: &[usize]) -> bool {
+        let mut total = 0;
+        for i in 0..cycle.len() {
+            let (from, to) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            match graph[from]
+                .iter()
+                .filter(|e| e.to == to)
+                .map(|e| e.weight)
+                .min()
+            {
+                Some(w) => total += w,
+                None => return false,
+            }
+        }
+        total < 0
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_bellman_ford_random(
+            (n, edges) in (1..10usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n, -3..10i64), 0..20))
+            })
+        ) {
+            let graph = Graph::from_edges(n, true, edges);
+            let cycle = negative_cycle(&graph);
+            if let Some(cycle) = &cycle {
+                assert!(is_negative_cycle(&graph, cycle));
+            }
+            for start in 0..n {
+                let distance = bellman_ford(&graph, start);
+                assert_eq!(spfa(&graph, start), distance.clone());
+                if distance.contains(&Some(NEG_INF)) {
+                    assert!(cycle.is_some());
+                }
+            }
+        }
+    }
+}
@@ -1,72 +1,398 @@
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+use crate::data_structure::radix_heap::RadixHeap;
+use crate::graph::Graph;
+use crate::math::modint::ModInt;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, VecDeque};
 
-#[derive(Clone, Debug)]
-pub struct Edge {
-    to: usize,
-    cost: usize,
+/// 最短経路問題で辺のコストとして使える型．
+/// 全順序を持ち，単位元 `zero` と加算 `add_cost` が定義されていればよい．
+pub trait Cost: Copy + Ord {
+    fn zero() -> Self;
+    fn add_cost(self, other: Self) -> Self;
 }
 
-pub struct Dijkstra {
-    // i番目の要素はi番目から出る`Edge`のを集めた`Vec`をもつ.
-    graph: Vec<Vec<Edge>>,
-    // i番目の要素はスタート地点からi番目の節点までの最短経路.
-    distance: Vec<usize>,
+macro_rules! impl_cost_for_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Cost for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn add_cost(self, other: Self) -> Self {
+                    self + other
+                }
+            }
+        )*
+    };
+}
+
+impl_cost_for_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// 符号なし整数のコスト．`u64` との相互変換を持ち，
+/// `Dijkstra::solve_with_buckets` と `Dijkstra::solve_with_radix_heap` で使える．
+pub trait UnsignedCost: Cost {
+    fn to_u64(self) -> u64;
+    fn from_u64(x: u64) -> Self;
+}
+
+macro_rules! impl_unsigned_cost {
+    ($($t:ty),*) => {
+        $(
+            impl UnsignedCost for $t {
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+
+                fn from_u64(x: u64) -> Self {
+                    x as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned_cost!(u8, u16, u32, u64, usize);
+
+/// `Dijkstra::solve_with_buckets` で扱える辺のコストの最大値．
+/// これを超えるとバケットの確保に時間とメモリがかかりすぎる．
+pub const MAX_BUCKET_COST: u64 = 1 << 20;
+
+/// タプルは辞書式順序で比較され，要素ごとに加算される．
+impl<A, B> Cost for (A, B)
+where
+    A: Cost,
+    B: Cost,
+{
+    fn zero() -> Self {
+        (A::zero(), B::zero())
+    }
+
+    fn add_cost(self, other: Self) -> Self {
+        (self.0.add_cost(other.0), self.1.add_cost(other.1))
+    }
+}
+
+impl<A, B, C> Cost for (A, B, C)
+where
+    A: Cost,
+    B: Cost,
+    C: Cost,
+{
+    fn zero() -> Self {
+        (A::zero(), B::zero(), C::zero())
+    }
+
+    fn add_cost(self, other: Self) -> Self {
+        (
+            self.0.add_cost(other.0),
+            self.1.add_cost(other.1),
+            self.2.add_cost(other.2),
+        )
+    }
+}
+
+/// `f64` に `f64::total_cmp` による全順序を入れたもの．
+/// `f64` をコストとして使うときはこれで包む．
+#[derive(Clone, Copy, Debug)]
+pub struct OrdF64(pub f64);
+
+impl PartialEq for OrdF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrdF64 {}
+
+impl PartialOrd for OrdF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrdF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Cost for OrdF64 {
+    fn zero() -> Self {
+        OrdF64(0.0)
+    }
+
+    fn add_cost(self, other: Self) -> Self {
+        OrdF64(self.0 + other.0)
+    }
+}
+
+pub struct Dijkstra<C>
+where
+    C: Cost,
+{
+    // 辺の重みをコストとする有向グラフ.
+    graph: Graph<C>,
+    // i番目の要素はスタート地点からi番目の節点までの最短経路. 到達できなければ `None`.
+    distance: Vec<Option<C>>,
+    // i番目の要素は最短経路上でi番目の節点の直前にある節点.
+    prev: Vec<Option<usize>>,
+    // 直前に解いたときの (始点, 始点の初期コスト).
+    starts: Vec<(usize, C)>,
 }
 
-impl Dijkstra {
+impl<C> Dijkstra<C>
+where
+    C: Cost,
+{
     /// Dijkstra 法で最短経路を求めるための構造体を初期化する．
     /// `size` は節点数．
     pub fn new(size: usize) -> Self {
-        Self {
-            graph: vec![vec![]; size],
-            distance: vec![usize::MAX; size],
-        }
+        Graph::directed(size).into()
     }
 
     /// `from` 番目の節点から `to` 番目の節点へコスト `cost` の辺を張る．
     /// `from` と `to` は 0-indexed でなければならない.
-    pub fn add_edge(&mut self, from: usize, to: usize, cost: usize) {
-        self.graph[from].push(Edge { to, cost });
+    /// `cost` は `C::zero()` 以上でなければならない．
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: C) {
+        self.graph.add_edge(from, to, cost);
+    }
+
+    /// 最短経路を求めるグラフを返す．
+    pub fn graph(&self) -> &Graph<C> {
+        &self.graph
     }
 
-    /// `to` 番目の節点への距離を返す
-    pub fn distance(&self, to: usize) -> usize {
+    /// `to` 番目の節点への距離を返す. 到達できなければ `None` を返す.
+    pub fn distance(&self, to: usize) -> Option<C> {
         self.distance[to]
     }
 
-    /// 最短経路を求める．
+    /// `start` 番目の節点からの最短経路を求める．
+    /// 以前に解いた結果は破棄される．
     pub fn solve(&mut self, start: usize) {
+        self.run(&[(start, C::zero())], None);
+    }
+
+    /// 複数の始点からの最短経路を求める．`starts` の各要素は (始点, 始点の初期コスト)．
+    /// 各節点への距離は，いずれかの始点から (初期コスト + 経路のコスト) の最小値になる．
+    pub fn solve_multi(&mut self, starts: &[(usize, C)]) {
+        self.run(starts, None);
+    }
+
+    /// `start` 番目の節点から `target` 番目の節点への最短経路が確定した時点で探索を打ち切り，
+    /// その距離を返す．`target` 以外の節点の距離は最短とは限らない．
+    pub fn solve_until(&mut self, start: usize, target: usize) -> Option<C> {
+        self.run(&[(start, C::zero())], Some(target));
+        self.distance[target]
+    }
+
+    fn reset(&mut self, starts: &[(usize, C)]) {
+        self.distance.iter_mut().for_each(|d| *d = None);
+        self.prev.iter_mut().for_each(|p| *p = None);
+        self.starts = starts.to_vec();
+    }
+
+    fn run(&mut self, starts: &[(usize, C)], target: Option<usize>) {
+        self.reset(starts);
         // (i番目の節点への最小コスト, i番目の節点) を格納する
         let mut bin_heap = BinaryHeap::new();
-        self.distance[start] = 0;
-        bin_heap.push(Reverse((0, start)));
-        while !bin_heap.is_empty() {
-            let (cost, current_edge_index) = bin_heap.pop().unwrap().0;
-            if self.distance[current_edge_index] < cost {
+        for &(start, cost) in starts {
+            if self.distance[start].is_none_or(|d| d > cost) {
+                self.distance[start] = Some(cost);
+                bin_heap.push(Reverse((cost, start)));
+            }
+        }
+        while let Some(Reverse((cost, current_edge_index))) = bin_heap.pop() {
+            if self.distance[current_edge_index] < Some(cost) {
                 continue;
             }
+            if target == Some(current_edge_index) {
+                break;
+            }
             for next_edge in &self.graph[current_edge_index] {
-                let candidate = self.distance[current_edge_index] + next_edge.cost;
-                if self.distance[next_edge.to] > candidate {
-                    self.distance[next_edge.to] = candidate;
-                    bin_heap.push(Reverse((self.distance[next_edge.to], next_edge.to)))
+                let candidate = cost.add_cost(next_edge.weight);
+                if self.distance[next_edge.to].is_none_or(|d| d > candidate) {
+                    self.distance[next_edge.to] = Some(candidate);
+                    self.prev[next_edge.to] = Some(current_edge_index);
+                    bin_heap.push(Reverse((candidate, next_edge.to)))
+                }
+            }
+        }
+    }
+
+    /// 最短経路木で `to` 番目の節点の親，すなわち最短経路上で直前にある節点を返す．
+    /// 始点や到達できない節点なら `None` を返す．
+    pub fn prev(&self, to: usize) -> Option<usize> {
+        self.prev[to]
+    }
+
+    /// 始点から `to` 番目の節点までの最短経路を，通る節点を順に並べて返す．
+    /// 到達できなければ `None` を返す．
+    pub fn path(&self, to: usize) -> Option<Vec<usize>> {
+        self.distance[to]?;
+        let mut path = vec![to];
+        let mut current = to;
+        while let Some(prev) = self.prev[current] {
+            path.push(prev);
+            current = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// 最短経路 DAG を返す．i番目の要素は，いずれかの最短経路に含まれる i番目の節点から出る辺の行き先．
+    /// 多重辺はその本数だけ含まれる．
+    pub fn shortest_path_dag(&self) -> Vec<Vec<usize>> {
+        (0..self.graph.len())
+            .map(|from| match self.distance[from] {
+                None => vec![],
+                Some(d) => self.graph[from]
+                    .iter()
+                    .filter(|e| self.distance[e.to] == Some(d.add_cost(e.weight)))
+                    .map(|e| e.to)
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// 始点から各節点への最短経路の本数を `ModInt` で返す．
+    /// 始点が複数ある場合は，初期コストが最短距離に一致する始点を一本の経路の起点として数える．
+    /// 到達できない節点は 0 になる．コスト 0 の閉路があってはならない．
+    pub fn count_paths(&self) -> Vec<ModInt> {
+        let n = self.graph.len();
+        let dag = self.shortest_path_dag();
+        let mut in_degree = vec![0; n];
+        for &to in dag.iter().flatten() {
+            in_degree[to] += 1;
+        }
+        let mut count = vec![ModInt::new(0); n];
+        for &(start, cost) in &self.starts {
+            if self.distance[start] == Some(cost) {
+                count[start] += ModInt::new(1);
+            }
+        }
+        let mut queue = (0..n)
+            .filter(|&v| self.distance[v].is_some() && in_degree[v] == 0)
+            .collect::<VecDeque<_>>();
+        while let Some(v) = queue.pop_front() {
+            for &to in &dag[v] {
+                count[to] = count[to] + count[v];
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    queue.push_back(to);
                 }
             }
         }
+        count
+    }
+}
+
+impl<C> Dijkstra<C>
+where
+    C: UnsignedCost,
+{
+    /// `start` 番目の節点からの最短経路を Dial 法 (バケットキュー) で求める．
+    /// 辺のコストの最大値を `W` として `O(nW + m)` で，`W` が小さいとき `solve` より速い．
+    /// バケットを `W + 1` 個確保するので，`W` が `MAX_BUCKET_COST` を超えるとパニックする．
+    pub fn solve_with_buckets(&mut self, start: usize) {
+        self.reset(&[(start, C::zero())]);
+        let max_cost = (0..self.graph.len())
+            .flat_map(|v| self.graph[v].iter().map(|e| e.weight.to_u64()))
+            .max()
+            .unwrap_or(0);
+        assert!(
+            max_cost <= MAX_BUCKET_COST,
+            "edge cost is too large for Dial's algorithm"
+        );
+        let n_buckets = max_cost + 1;
+        // 距離 d の節点は `buckets[d % n_buckets]` に入る．
+        // 確定していない節点の距離は常に [d, d + max_cost] にあるので衝突しない．
+        let mut buckets = vec![vec![]; n_buckets as usize];
+        self.distance[start] = Some(C::zero());
+        buckets[0].push(start);
+        let mut n_remaining = 1;
+        let mut cost = 0;
+        while n_remaining > 0 {
+            while let Some(current) = buckets[(cost % n_buckets) as usize].pop() {
+                n_remaining -= 1;
+                if self.distance[current] != Some(C::from_u64(cost)) {
+                    continue;
+                }
+                for next_edge in &self.graph[current] {
+                    let candidate = C::from_u64(cost).add_cost(next_edge.weight);
+                    if self.distance[next_edge.to].is_none_or(|d| d > candidate) {
+                        self.distance[next_edge.to] = Some(candidate);
+                        self.prev[next_edge.to] = Some(current);
+                        buckets[(candidate.to_u64() % n_buckets) as usize].push(next_edge.to);
+                        n_remaining += 1;
+                    }
+                }
+            }
+            cost += 1;
+        }
+    }
+
+    /// `start` 番目の節点からの最短経路を `BinaryHeap` の代わりに `RadixHeap` を用いて求める．
+    pub fn solve_with_radix_heap(&mut self, start: usize) {
+        self.reset(&[(start, C::zero())]);
+        let mut radix_heap = RadixHeap::new();
+        self.distance[start] = Some(C::zero());
+        radix_heap.push(0, start);
+        while let Some((cost, current)) = radix_heap.pop() {
+            let cost = C::from_u64(cost);
+            if self.distance[current] < Some(cost) {
+                continue;
+            }
+            for next_edge in &self.graph[current] {
+                let candidate = cost.add_cost(next_edge.weight);
+                if self.distance[next_edge.to].is_none_or(|d| d > candidate) {
+                    self.distance[next_edge.to] = Some(candidate);
+                    self.prev[next_edge.to] = Some(current);
+                    radix_heap.push(candidate.to_u64(), next_edge.to);
+                }
+            }
+        }
+    }
+}
+
+/// 辺の重みをコストとするグラフから構造体を作る．
+/// 無向グラフなら各辺を両方向に通れるものとして扱う．
+impl<C> From<Graph<C>> for Dijkstra<C>
+where
+    C: Cost,
+{
+    fn from(graph: Graph<C>) -> Self {
+        let size = graph.len();
+        Self {
+            graph,
+            distance: vec![None; size],
+            prev: vec![None; size],
+            starts: vec![],
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::strategy::{Just, Strategy};
 
     #[test]
     fn cost_of_start_point_is_zero() {
         let mut dijkstra = Dijkstra::new(2);
-        dijkstra.add_edge(0, 1, 1);
+        dijkstra.add_edge(0, 1, 1usize);
         dijkstra.solve(0);
-        assert_eq!(0, dijkstra.distance(0));
+        assert_eq!(Some(0), dijkstra.distance(0));
+    }
+
+    #[test]
+    fn unreachable_node_has_no_distance() {
+        let mut dijkstra = Dijkstra::new(3);
+        dijkstra.add_edge(0, 1, u64::MAX);
+        dijkstra.solve(0);
+        assert_eq!(Some(u64::MAX), dijkstra.distance(1));
+        assert_eq!(None, dijkstra.distance(2));
     }
 
     #[test]
@@ -84,6 +410,158 @@ mod tests {
         dijkstra.add_edge(5, 7, 2);
         dijkstra.add_edge(6, 7, 5);
         dijkstra.solve(0);
-        assert_eq!(8, dijkstra.distance(7));
+        assert_eq!(Some(8i64), dijkstra.distance(7));
+    }
+
+    #[test]
+    fn lexicographic_cost_by_tuple() {
+        // (距離, 使った辺の本数) の辞書式順序で最小化する．
+        let mut dijkstra = Dijkstra::new(4);
+        dijkstra.add_edge(0, 1, (1u32, 1u32));
+        dijkstra.add_edge(1, 3, (1, 1));
+        dijkstra.add_edge(0, 3, (2, 1));
+        dijkstra.add_edge(0, 2, (1, 1));
+        dijkstra.add_edge(2, 3, (0, 1));
+        dijkstra.solve(0);
+        assert_eq!(Some((1, 2)), dijkstra.distance(3));
+    }
+
+    #[test]
+    fn floating_point_cost() {
+        let mut dijkstra = Dijkstra::new(3);
+        dijkstra.add_edge(0, 1, OrdF64(0.5));
+        dijkstra.add_edge(1, 2, OrdF64(0.25));
+        dijkstra.add_edge(0, 2, OrdF64(1.0));
+        dijkstra.solve(0);
+        assert_eq!(Some(OrdF64(0.75)), dijkstra.distance(2));
+    }
+
+    #[test]
+    fn path_reconstruction() {
+        let mut dijkstra = Dijkstra::new(5);
+        dijkstra.add_edge(0, 1, 2);
+        dijkstra.add_edge(0, 2, 5);
+        dijkstra.add_edge(1, 2, 1);
+        dijkstra.add_edge(2, 3, 1);
+        dijkstra.solve(0);
+        assert_eq!(Some(vec![0]), dijkstra.path(0));
+        assert_eq!(Some(vec![0, 1, 2, 3]), dijkstra.path(3));
+        assert_eq!(None, dijkstra.path(4));
+    }
+
+    #[test]
+    fn count_shortest_paths() {
+        // 0 -> {1, 2} -> 3 -> {4, 5} -> 6 の菱形が二つ連なったグラフ．
+        let mut dijkstra = Dijkstra::new(8);
+        for &(from, to) in &[
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (3, 5),
+            (4, 6),
+            (5, 6),
+        ] {
+            dijkstra.add_edge(from, to, 1u32);
+        }
+        dijkstra.add_edge(0, 6, 5);
+        dijkstra.add_edge(0, 3, 0);
+        dijkstra.solve(0);
+        let count = dijkstra.count_paths();
+        assert_eq!(count[3], ModInt::new(1));
+        assert_eq!(count[6], ModInt::new(2));
+        assert_eq!(count[7], ModInt::new(0));
+        assert_eq!(dijkstra.shortest_path_dag()[0], vec![1, 2, 3]);
+        assert_eq!(dijkstra.shortest_path_dag()[1], Vec::<usize>::new());
+    }
+
+    #[test]
+    fn solve_twice_resets_distance() {
+        let mut dijkstra = Dijkstra::new(3);
+        dijkstra.add_edge(0, 1, 1);
+        dijkstra.add_edge(1, 2, 1);
+        dijkstra.solve(0);
+        assert_eq!(Some(2), dijkstra.distance(2));
+        dijkstra.solve(1);
+        assert_eq!(None, dijkstra.distance(0));
+        assert_eq!(Some(1), dijkstra.distance(2));
+        assert_eq!(Some(vec![1, 2]), dijkstra.path(2));
+    }
+
+    #[test]
+    fn multi_source_with_initial_costs() {
+        let mut dijkstra = Dijkstra::new(4);
+        dijkstra.add_edge(0, 2, 1);
+        dijkstra.add_edge(1, 2, 1);
+        dijkstra.add_edge(2, 3, 1);
+        dijkstra.solve_multi(&[(0, 5), (1, 3)]);
+        assert_eq!(Some(5), dijkstra.distance(0));
+        assert_eq!(Some(4), dijkstra.distance(2));
+        assert_eq!(Some(vec![1, 2, 3]), dijkstra.path(3));
+        dijkstra.solve_multi(&[(0, 0), (1, 0)]);
+        assert_eq!(dijkstra.count_paths()[3], ModInt::new(2));
+    }
+
+    #[test]
+    fn solve_until_target() {
+        let mut dijkstra = Dijkstra::new(4);
+        dijkstra.add_edge(0, 1, 1);
+        dijkstra.add_edge(1, 2, 1);
+        dijkstra.add_edge(2, 3, 1);
+        assert_eq!(Some(1), dijkstra.solve_until(0, 1));
+        assert_eq!(None, dijkstra.distance(3));
+        assert_eq!(None, dijkstra.solve_until(3, 0));
+    }
+
+    #[test]
+    fn solve_on_undirected_graph() {
+        let graph = Graph::from_edges(3, false, vec![(0, 1, 3), (1, 2, 4), (2, 0, 10)]);
+        let mut dijkstra = Dijkstra::from(graph);
+        dijkstra.solve(2);
+        assert_eq!(Some(4), dijkstra.distance(1));
+        assert_eq!(Some(7), dijkstra.distance(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "edge cost is too large for Dial's algorithm")]
+    fn solve_with_buckets_rejects_large_cost() {
+        let mut dijkstra = Dijkstra::new(2);
+        dijkstra.add_edge(0, 1, usize::MAX / 2);
+        dijkstra.solve_with_buckets(0);
+    }
+
+    #[test]
+    fn solve_with_radix_heap_handles_large_cost() {
+        let mut dijkstra = Dijkstra::new(3);
+        dijkstra.add_edge(0, 1, u64::MAX / 2);
+        dijkstra.add_edge(1, 2, 1u64);
+        dijkstra.solve_with_radix_heap(0);
+        assert_eq!(Some(u64::MAX / 2 + 1), dijkstra.distance(2));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_buckets_and_radix_heap_random(
+            (n, edges) in (1..30usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n, 0..10u32), 0..60))
+            })
+        ) {
+            let mut dijkstra = Dijkstra::from(Graph::from_edges(n, true, edges));
+            for start in 0..n {
+                dijkstra.solve(start);
+                let expected = (0..n).map(|v| dijkstra.distance(v)).collect::<Vec<_>>();
+                dijkstra.solve_with_buckets(start);
+                for (v, &d) in expected.iter().enumerate() {
+                    assert_eq!(dijkstra.distance(v), d);
+                    assert_eq!(dijkstra.path(v).is_some(), d.is_some());
+                }
+                dijkstra.solve_with_radix_heap(start);
+                for (v, &d) in expected.iter().enumerate() {
+                    assert_eq!(dijkstra.distance(v), d);
+                    assert_eq!(dijkstra.path(v).is_some(), d.is_some());
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,212 @@
+use crate::graph::adjacent4;
+use proconio::marker::Chars;
+use proconio::source::Source;
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::ops::Index;
+
+/// 二次元のグリッド．`grid[(row, col)]` で各マスの値が得られる．
+#[derive(Clone, Debug)]
+pub struct Grid<T> {
+    cells: Vec<Vec<T>>,
+}
+
+impl<T> Grid<T> {
+    /// 二次元配列からグリッドを作る．各行の長さは等しくなければならない．
+    pub fn new(cells: Vec<Vec<T>>) -> Self {
+        assert!(cells.iter().all(|row| row.len() == cells[0].len()));
+        Self { cells }
+    }
+
+    /// 行数を返す．
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// 列数を返す．
+    pub fn width(&self) -> usize {
+        self.cells.first().map_or(0, |row| row.len())
+    }
+
+    /// 条件を満たすマスを行優先で探し，最初に見つかったものを返す．
+    pub fn position<F>(&self, predicate: F) -> Option<(usize, usize)>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.cells
+            .iter()
+            .enumerate()
+            .find_map(|(row, cells)| cells.iter().position(&predicate).map(|col| (row, col)))
+    }
+
+    /// `start` から上下左右に移動するときの各マスへの最短手数を返す．
+    /// `is_wall` を満たすマスには入れず，到達できないマスは `None` になる．
+    pub fn bfs<F>(&self, start: (usize, usize), is_wall: F) -> Vec<Vec<Option<usize>>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.multi_source_bfs(&[start], is_wall)
+    }
+
+    /// `starts` のいずれかから上下左右に移動するときの各マスへの最短手数を返す．
+    /// `is_wall` を満たすマスには入れず，到達できないマスは `None` になる．
+    pub fn multi_source_bfs<F>(
+        &self,
+        starts: &[(usize, usize)],
+        is_wall: F,
+    ) -> Vec<Vec<Option<usize>>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut distance = vec![vec![None; self.width()]; self.height()];
+        let mut queue = VecDeque::new();
+        for &(row, col) in starts {
+            if distance[row][col].is_none() {
+                distance[row][col] = Some(0);
+                queue.push_back((row, col));
+            }
+        }
+        while let Some((row, col)) = queue.pop_front() {
+            let d = distance[row][col].unwrap();
+            for (next_row, next_col) in adjacent4(&self.cells, row, col) {
+                if distance[next_row][next_col].is_none()
+                    && !is_wall(&self.cells[next_row][next_col])
+                {
+                    distance[next_row][next_col] = Some(d + 1);
+                    queue.push_back((next_row, next_col));
+                }
+            }
+        }
+        distance
+    }
+
+    /// `start` から上下左右に移動するときの各マスへの最小コストを 0-1 BFS で求める．
+    /// `cost(from, to)` は隣接するマス `from` から `to` へ移動するコストで，0 か 1 でなければならない．
+    /// 移動できない場合は `cost` が `None` を返す．2 以上のコストを返すとパニックする．
+    /// 到達できないマスは `None` になる．
+    pub fn zero_one_bfs<F>(&self, start: (usize, usize), cost: F) -> Vec<Vec<Option<usize>>>
+    where
+        F: Fn(&T, &T) -> Option<usize>,
+    {
+        let mut distance: Vec<Vec<Option<usize>>> = vec![vec![None; self.width()]; self.height()];
+        let mut deque = VecDeque::new();
+        distance[start.0][start.1] = Some(0);
+        deque.push_back((0, start));
+        while let Some((d, (row, col))) = deque.pop_front() {
+            if distance[row][col] < Some(d) {
+                continue;
+            }
+            for (next_row, next_col) in adjacent4(&self.cells, row, col) {
+                let c = match cost(&self.cells[row][col], &self.cells[next_row][next_col]) {
+                    Some(c) => c,
+                    None => continue,
+                };
+                assert!(c <= 1, "the cost of a move must be 0 or 1");
+                let candidate = d + c;
+                if distance[next_row][next_col].is_none_or(|current| current > candidate) {
+                    distance[next_row][next_col] = Some(candidate);
+                    if c == 0 {
+                        deque.push_front((candidate, (next_row, next_col)));
+                    } else {
+                        deque.push_back((candidate, (next_row, next_col)));
+                    }
+                }
+            }
+        }
+        distance
+    }
+}
+
+impl<T> From<Vec<Vec<T>>> for Grid<T> {
+    fn from(cells: Vec<Vec<T>>) -> Self {
+        Self::new(cells)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.cells[row][col]
+    }
+}
+
+/// "H W" の後に H 行の文字列が続く形式のグリッドを読み込む．
+/// 長さが W でない行があるとパニックする．
+pub fn read_grid<R, S>(source: &mut S) -> Grid<char>
+where
+    R: BufRead,
+    S: Source<R>,
+{
+    proconio::input! {
+        from &mut *source,
+        h: usize,
+        w: usize,
+        cells: [Chars; h],
+    }
+    for (i, row) in cells.iter().enumerate() {
+        assert_eq!(row.len(), w, "row {i} of the grid must have length {w}");
+    }
+    Grid::new(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proconio::source::once::OnceSource;
+
+    fn maze() -> Grid<char> {
+        let mut source = OnceSource::from("3 4\nS.#.\n.##.\n...G\n");
+        read_grid(&mut source)
+    }
+
+    #[test]
+    fn test_read_grid() {
+        let grid = maze();
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.width(), 4);
+        assert_eq!(grid[(0, 2)], '#');
+        assert_eq!(grid.position(|&c| c == 'G'), Some((2, 3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 of the grid must have length 4")]
+    fn test_read_grid_rejects_wrong_width() {
+        let mut source = OnceSource::from("2 4\nS.#.\n.#\n");
+        read_grid(&mut source);
+    }
+
+    #[test]
+    fn test_grid_bfs() {
+        let grid = maze();
+        let distance = grid.bfs((0, 0), |&c| c == '#');
+        assert_eq!(distance[2][3], Some(5));
+        assert_eq!(distance[0][3], Some(7));
+        assert_eq!(distance[0][2], None);
+    }
+
+    #[test]
+    fn test_grid_multi_source_bfs() {
+        let grid = maze();
+        let distance = grid.multi_source_bfs(&[(0, 0), (2, 3)], |&c| c == '#');
+        assert_eq!(distance[2][1], Some(2));
+        assert_eq!(distance[0][3], Some(2));
+    }
+
+    #[test]
+    fn test_grid_zero_one_bfs() {
+        // 壁を壊して進むのにコスト 1 かかる．
+        let grid = maze();
+        let distance = grid.zero_one_bfs((0, 0), |_, &to| Some(if to == '#' { 1 } else { 0 }));
+        assert_eq!(distance[2][3], Some(0));
+        assert_eq!(distance[0][2], Some(1));
+        assert_eq!(distance[1][2], Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "the cost of a move must be 0 or 1")]
+    fn test_grid_zero_one_bfs_rejects_large_cost() {
+        let grid = maze();
+        grid.zero_one_bfs((0, 0), |_, _| Some(2));
+    }
+}
@@ -0,0 +1,340 @@
+use crate::graph::dijkstra::{Cost, Dijkstra};
+use crate::graph::Graph;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap};
+use std::ops::Sub;
+use std::rc::Rc;
+
+/// 永続 leftist heap の節点．`to` は迂回辺の行き先．
+struct HeapNode<C> {
+    key: C,
+    to: usize,
+    rank: usize,
+    left: Option<Rc<HeapNode<C>>>,
+    right: Option<Rc<HeapNode<C>>>,
+}
+
+fn rank<C>(heap: &Option<Rc<HeapNode<C>>>) -> usize {
+    heap.as_ref().map_or(0, |node| node.rank)
+}
+
+/// 二つのヒープを併合した新しいヒープを返す．元のヒープは変更しない．`O(log n)`．
+fn merge<C>(a: Option<Rc<HeapNode<C>>>, b: Option<Rc<HeapNode<C>>>) -> Option<Rc<HeapNode<C>>>
+where
+    C: Cost,
+{
+    let (a, b) = match (a, b) {
+        (None, heap) | (heap, None) => return heap,
+        (Some(a), Some(b)) => {
+            if a.key <= b.key {
+                (a, b)
+            } else {
+                (b, a)
+            }
+        }
+    };
+    let merged = merge(a.right.clone(), Some(b));
+    let (left, right) = if rank(&a.left) >= rank(&merged) {
+        (a.left.clone(), merged)
+    } else {
+        (merged, a.left.clone())
+    };
+    Some(Rc::new(HeapNode {
+        key: a.key,
+        to: a.to,
+        rank: rank(&right) + 1,
+        left,
+        right,
+    }))
+}
+
+/// Eppstein 法で `start` 番目の節点から `goal` 番目の節点への経路の長さを短い順に `k` 個求める．
+/// 経路は同じ節点や辺を何度通ってもよい (walk)．多重辺はそれぞれ別の経路として数える．
+/// 経路が `k` 個なければ存在するものだけを返す．`O(m log m + k log k)`．
+pub fn k_shortest_paths<C>(graph: &Graph<C>, start: usize, goal: usize, k: usize) -> Vec<C>
+where
+    C: Cost + Sub<Output = C>,
+{
+    let n = graph.len();
+    let mut dijkstra = Dijkstra::from(graph.reversed());
+    dijkstra.solve(goal);
+    let distance = (0..n).map(|v| dijkstra.distance(v)).collect::<Vec<_>>();
+    if distance[start].is_none() || k == 0 {
+        return vec![];
+    }
+
+    // `goal` を根とする最短経路木の子を列挙し，根から順にヒープを作る．
+    let mut children = vec![vec![]; n];
+    for v in 0..n {
+        if let Some(parent) = dijkstra.prev(v) {
+            children[parent].push(v);
+        }
+    }
+    let mut heaps: Vec<Option<Rc<HeapNode<C>>>> = vec![None; n];
+    let mut stack = vec![goal];
+    while let Some(v) = stack.pop() {
+        let d = distance[v].unwrap();
+        let parent = dijkstra.prev(v);
+        let mut heap = parent.and_then(|p| heaps[p].clone());
+        let mut tree_edge_found = false;
+        for edge in &graph[v] {
+            let d_to = match distance[edge.to] {
+                Some(d_to) => d_to,
+                None => continue,
+            };
+            let length = edge.weight.add_cost(d_to);
+            // 最短経路木の辺を一本だけ除き，残りの辺を迂回のコストをキーとしてヒープに入れる．
+            if !tree_edge_found && Some(edge.to) == parent && length == d {
+                tree_edge_found = true;
+                continue;
+            }
+            let node = HeapNode {
+                key: length - d,
+                to: edge.to,
+                rank: 1,
+                left: None,
+                right: None,
+            };
+            heap = merge(heap, Some(Rc::new(node)));
+        }
+        heaps[v] = heap;
+        stack.extend_from_slice(&children[v]);
+    }
+
+    let mut result = vec![distance[start].unwrap()];
+    let mut bin_heap = BinaryHeap::new();
+    if let Some(root) = &heaps[start] {
+        bin_heap.push(Reverse(HeapEntry(
+            distance[start].unwrap().add_cost(root.key),
+            root.clone(),
+        )));
+    }
+    while result.len() < k {
+        let Reverse(HeapEntry(cost, node)) = match bin_heap.pop() {
+            Some(entry) => entry,
+            None => break,
+        };
+        result.push(cost);
+        // 最後に使った迂回辺を，ヒープ内で次に小さい迂回辺に取り替える．
+        for child in [&node.left, &node.right].into_iter().flatten() {
+            let next = cost - node.key;
+            bin_heap.push(Reverse(HeapEntry(next.add_cost(child.key), child.clone())));
+        }
+        // 最後に使った迂回辺の先でさらに迂回する．
+        if let Some(root) = &heaps[node.to] {
+            bin_heap.push(Reverse(HeapEntry(cost.add_cost(root.key), root.clone())));
+        }
+    }
+    result
+}
+
+/// `BinaryHeap` に入れるための，コストのみで比較される組．
+struct HeapEntry<C>(C, Rc<HeapNode<C>>);
+
+impl<C: Cost> PartialEq for HeapEntry<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<C: Cost> Eq for HeapEntry<C> {}
+
+impl<C: Cost> PartialOrd for HeapEntry<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Cost> Ord for HeapEntry<C> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Yen 法で `start` 番目の節点から `goal` 番目の節点への単純パスを短い順に `k` 個求める．
+/// 各要素は (経路の長さ, 通る節点の列)．節点の列が同じ経路は一つとして数える．
+/// 経路が `k` 個なければ存在するものだけを返す．`O(kn (n + m) log n)`．
+pub fn yen<C>(graph: &Graph<C>, start: usize, goal: usize, k: usize) -> Vec<(C, Vec<usize>)>
+where
+    C: Cost,
+{
+    let n = graph.len();
+    let edge_cost = |from: usize, to: usize| {
+        graph[from]
+            .iter()
+            .filter(|e| e.to == to)
+            .map(|e| e.weight)
+            .min()
+            .unwrap()
+    };
+
+    let mut dijkstra = Dijkstra::from(graph.clone());
+    let mut result = vec![];
+    match dijkstra.solve_until(start, goal) {
+        Some(cost) if k > 0 => result.push((cost, dijkstra.path(goal).unwrap())),
+        _ => return result,
+    }
+    let mut candidates = BTreeSet::new();
+    while result.len() < k {
+        let last = result.last().unwrap().1.clone();
+        let mut root_cost = C::zero();
+        for i in 0..last.len() - 1 {
+            let spur = last[i];
+            let root = &last[..=i];
+            // 既に見つかった経路のうち根の部分が一致するものについて，その次の辺を使えなくする．
+            let blocked_edges = result
+                .iter()
+                .filter(|(_, path)| path.len() > i + 1 && &path[..=i] == root)
+                .map(|(_, path)| (path[i], path[i + 1]))
+                .collect::<Vec<_>>();
+            let mut blocked = vec![false; n];
+            root[..i].iter().for_each(|&v| blocked[v] = true);
+
+            let mut spur_graph = Graph::directed(n);
+            for v in (0..n).filter(|&v| !blocked[v]) {
+                for edge in &graph[v] {
+                    if !blocked[edge.to] && !blocked_edges.contains(&(v, edge.to)) {
+                        spur_graph.add_edge(v, edge.to, edge.weight);
+                    }
+                }
+            }
+            let mut dijkstra = Dijkstra::from(spur_graph);
+            if let Some(cost) = dijkstra.solve_until(spur, goal) {
+                let mut path = root[..i].to_vec();
+                path.extend(dijkstra.path(goal).unwrap());
+                candidates.insert((root_cost.add_cost(cost), path));
+            }
+            root_cost = root_cost.add_cost(edge_cost(last[i], last[i + 1]));
+        }
+        match candidates.pop_first() {
+            Some(candidate) => result.push(candidate),
+            None => break,
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::{Just, Strategy};
+
+    /// 長さ `max_length` 以下の経路をすべて列挙して長さを昇順に返す．
+    fn naive_walks(graph: &Graph<u32>, start: usize, goal: usize, max_length: u32) -> Vec<u32> {
+        // `goal` に到達できない節点へは進まない．
+        let mut reachable = vec![false; graph.len()];
+        let reversed = graph.reversed();
+        let mut stack = vec![goal];
+        reachable[goal] = true;
+        while let Some(v) = stack.pop() {
+            for edge in &reversed[v] {
+                if !reachable[edge.to] {
+                    reachable[edge.to] = true;
+                    stack.push(edge.to);
+                }
+            }
+        }
+
+        let mut result = vec![];
+        let mut stack = vec![(start, 0)];
+        while let Some((v, cost)) = stack.pop() {
+            if v == goal {
+                result.push(cost);
+            }
+            for edge in &graph[v] {
+                if reachable[edge.to] && cost + edge.weight <= max_length {
+                    stack.push((edge.to, cost + edge.weight));
+                }
+            }
+        }
+        result.sort();
+        result
+    }
+
+    /// 単純パスをすべて列挙して (長さ, 経路) を昇順に返す．
+    fn naive_simple_paths(graph: &Graph<u32>, start: usize, goal: usize) -> Vec<(u32, Vec<usize>)> {
+        fn dfs(
+            graph: &Graph<u32>,
+            goal: usize,
+            path: &mut Vec<usize>,
+            cost: u32,
+            result: &mut BTreeSet<(u32, Vec<usize>)>,
+        ) {
+            let v = *path.last().unwrap();
+            if v == goal {
+                result.insert((cost, path.clone()));
+                return;
+            }
+            for edge in &graph[v] {
+                if !path.contains(&edge.to) {
+                    path.push(edge.to);
+                    dfs(graph, goal, path, cost + edge.weight, result);
+                    path.pop();
+                }
+            }
+        }
+        let mut result = BTreeSet::new();
+        dfs(graph, goal, &mut vec![start], 0, &mut result);
+        // 節点の列が同じ経路は最も短いものだけを残す．
+        let mut seen = BTreeSet::new();
+        result
+            .into_iter()
+            .filter(|(_, path)| seen.insert(path.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_k_shortest_paths() {
+        let graph = Graph::from_edges(
+            4,
+            true,
+            vec![
+                (0, 1, 1u32),
+                (0, 2, 2),
+                (1, 3, 2),
+                (2, 3, 2),
+                (1, 2, 0),
+                (3, 0, 1),
+            ],
+        );
+        assert_eq!(k_shortest_paths(&graph, 0, 3, 5), vec![3, 3, 4, 7, 7]);
+        let paths = yen(&graph, 0, 3, 5);
+        assert_eq!(
+            paths,
+            vec![
+                (3, vec![0, 1, 3]),
+                (3, vec![0, 1, 2, 3]),
+                (4, vec![0, 2, 3])
+            ]
+        );
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_k_shortest_paths_random(
+            (n, edges) in (2..6usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n, 1..5u32), 0..10))
+            })
+        ) {
+            let graph = Graph::from_edges(n, true, edges);
+            let k = 10;
+            let actual = k_shortest_paths(&graph, 0, n - 1, k);
+            let max_length = actual.last().cloned().unwrap_or(0);
+            let expected = naive_walks(&graph, 0, n - 1, max_length);
+            assert_eq!(&actual[..], &expected[..actual.len()]);
+            if actual.len() < k {
+                // 経路が k 個未満なら閉路を通る経路はなく，どの経路も辺の重みの総和より短い．
+                let total = graph.edges().iter().map(|e| e.weight).sum();
+                assert_eq!(actual.len(), naive_walks(&graph, 0, n - 1, total).len());
+            }
+
+            let actual = yen(&graph, 0, n - 1, k);
+            let expected = naive_simple_paths(&graph, 0, n - 1);
+            assert_eq!(actual.len(), expected.len().min(k));
+            for ((actual_cost, actual_path), (expected_cost, _)) in actual.iter().zip(&expected) {
+                assert_eq!(actual_cost, expected_cost);
+                assert!(expected.iter().any(|(c, p)| c == actual_cost && p == actual_path));
+            }
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use crate::graph::scc::component_ids;
+use crate::graph::Graph;
+
+/// 無向グラフの深さ優先探索の行きがけ順 `order` と lowlink `low` を求め，橋，関節点，二重頂点連結成分を列挙する．
+/// `low[v]` は `v` から DFS 木の辺を下り，後退辺を高々一本使って到達できる節点の `order` の最小値．
+/// 多重辺は区別し，自己ループは無視する．再帰を使わないので深いグラフでもスタックオーバーフローしない．
+pub struct Lowlink {
+    order: Vec<usize>,
+    low: Vec<usize>,
+    bridges: Vec<usize>,
+    articulation_points: Vec<usize>,
+    biconnected_components: Vec<Vec<usize>>,
+}
+
+impl Lowlink {
+    /// 無向グラフ `graph` について求める．`O(n + m)`．
+    pub fn new<W>(graph: &Graph<W>) -> Self {
+        let n = graph.len();
+        let mut order = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut bridges = vec![];
+        let mut is_articulation = vec![false; n];
+        let mut biconnected_components = vec![];
+        let mut n_visited = 0;
+        // 二重頂点連結成分を切り出すための，訪問した節点のスタック．
+        let mut visited = vec![];
+        // (節点, 親から来た辺の id, 次に調べる辺の添字) を積む．
+        let mut call_stack: Vec<(usize, Option<usize>, usize)> = vec![];
+        for root in 0..n {
+            if order[root] != usize::MAX {
+                continue;
+            }
+            order[root] = n_visited;
+            low[root] = n_visited;
+            n_visited += 1;
+            visited.push(root);
+            call_stack.push((root, None, 0));
+            let mut n_root_children = 0;
+            while let Some((v, parent_edge, i)) = call_stack.last_mut() {
+                let (v, parent_edge) = (*v, *parent_edge);
+                if let Some(edge) = graph[v].get(*i) {
+                    *i += 1;
+                    if edge.to == v || Some(edge.id) == parent_edge {
+                        continue;
+                    }
+                    if order[edge.to] == usize::MAX {
+                        order[edge.to] = n_visited;
+                        low[edge.to] = n_visited;
+                        n_visited += 1;
+                        visited.push(edge.to);
+                        call_stack.push((edge.to, Some(edge.id), 0));
+                    } else {
+                        low[v] = low[v].min(order[edge.to]);
+                    }
+                    continue;
+                }
+                call_stack.pop();
+                let p = match call_stack.last() {
+                    Some(&(p, _, _)) => p,
+                    None => {
+                        if n_root_children == 0 {
+                            biconnected_components.push(vec![visited.pop().unwrap()]);
+                        }
+                        continue;
+                    }
+                };
+                low[p] = low[p].min(low[v]);
+                if low[v] > order[p] {
+                    bridges.push(parent_edge.unwrap());
+                }
+                if p == root {
+                    n_root_children += 1;
+                }
+                if low[v] >= order[p] {
+                    if p != root {
+                        is_articulation[p] = true;
+                    }
+                    // `v` 以降に訪問した節点と `p` が一つの成分になる．
+                    let mut component = vec![p];
+                    loop {
+                        let u = visited.pop().unwrap();
+                        component.push(u);
+                        if u == v {
+                            break;
+                        }
+                    }
+                    biconnected_components.push(component);
+                }
+            }
+            if n_root_children >= 2 {
+                is_articulation[root] = true;
+            }
+            visited.clear();
+        }
+        bridges.sort();
+        Self {
+            order,
+            low,
+            bridges,
+            articulation_points: (0..n).filter(|&v| is_articulation[v]).collect(),
+            biconnected_components,
+        }
+    }
+
+    /// `v` 番目の節点の行きがけ順の番号を返す．
+    pub fn order(&self, v: usize) -> usize {
+        self.order[v]
+    }
+
+    /// `v` 番目の節点の lowlink を返す．
+    pub fn low(&self, v: usize) -> usize {
+        self.low[v]
+    }
+
+    /// 橋の `id` を昇順に返す．
+    pub fn bridges(&self) -> &[usize] {
+        &self.bridges
+    }
+
+    /// 関節点を昇順に返す．
+    pub fn articulation_points(&self) -> &[usize] {
+        &self.articulation_points
+    }
+
+    /// 二重頂点連結成分の節点の集合を返す．
+    /// 関節点は複数の成分に含まれ，孤立点は一つの節点だけからなる成分になる．
+    pub fn biconnected_components(&self) -> &[Vec<usize>] {
+        &self.biconnected_components
+    }
+
+    /// 二重辺連結成分 (橋を取り除いたときの連結成分) の節点の集合を返す．`O(n + m)`．
+    pub fn two_edge_connected_components<W>(&self, graph: &Graph<W>) -> Vec<Vec<usize>> {
+        let n = graph.len();
+        let mut is_bridge = vec![false; graph.n_edges()];
+        self.bridges.iter().for_each(|&id| is_bridge[id] = true);
+        let mut seen = vec![false; n];
+        let mut components = vec![];
+        for start in 0..n {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![start];
+            let mut head = 0;
+            while let Some(&v) = component.get(head) {
+                head += 1;
+                for edge in &graph[v] {
+                    if !is_bridge[edge.id] && !seen[edge.to] {
+                        seen[edge.to] = true;
+                        component.push(edge.to);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+}
+
+/// 二重辺連結成分を一つの節点に縮約した森 (橋の木) を作る．節点 `i` は `components[i]` に対応する．
+/// 橋が重みを保ったまま森の辺になる．
+pub fn bridge_tree<W>(graph: &Graph<W>, components: &[Vec<usize>]) -> Graph<W>
+where
+    W: Clone,
+{
+    let ids = component_ids(graph.len(), components);
+    let mut tree = Graph::undirected(components.len());
+    for edge in graph.edges() {
+        if ids[edge.from] != ids[edge.to] {
+            tree.add_edge(ids[edge.from], ids[edge.to], edge.weight.clone());
+        }
+    }
+    tree
+}
+
+/// 節点数 `n` のグラフの二重頂点連結成分 `components` からブロックカット木を作る．
+/// 節点 `v < n` は元のグラフの節点，節点 `n + i` は `components[i]` に対応し，
+/// 各成分とそれに含まれる節点を辺で結ぶ．関節点でない節点は葉になる．
+pub fn block_cut_tree(n: usize, components: &[Vec<usize>]) -> Graph<()> {
+    let mut tree = Graph::undirected(n + components.len());
+    for (i, component) in components.iter().enumerate() {
+        for &v in component {
+            tree.add_edge(n + i, v, ());
+        }
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::{Just, Strategy};
+
+    /// `removed_vertex` と `removed_edge` を使わずに連結成分を数える．
+    fn count_components(
+        graph: &Graph<()>,
+        removed_vertex: Option<usize>,
+        removed_edge: Option<usize>,
+    ) -> usize {
+        let n = graph.len();
+        let mut seen = vec![false; n];
+        let mut count = 0;
+        for start in (0..n).filter(|&v| Some(v) != removed_vertex) {
+            if seen[start] {
+                continue;
+            }
+            count += 1;
+            seen[start] = true;
+            let mut stack = vec![start];
+            while let Some(v) = stack.pop() {
+                for edge in &graph[v] {
+                    if Some(edge.id) != removed_edge
+                        && Some(edge.to) != removed_vertex
+                        && !seen[edge.to]
+                    {
+                        seen[edge.to] = true;
+                        stack.push(edge.to);
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn test_lowlink() {
+        // 0 - 1 - 2 - 0 の三角形に，2 - 3 - 4 と 3 - 4 の多重辺をつなぐ．5 は孤立点．
+        let graph = Graph::from_edges(
+            6,
+            false,
+            vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (3, 4)],
+        );
+        let lowlink = Lowlink::new(&graph);
+        assert_eq!(lowlink.bridges(), &[3]);
+        assert_eq!(lowlink.articulation_points(), &[2, 3]);
+        let mut components = lowlink
+            .biconnected_components()
+            .iter()
+            .map(|c| {
+                let mut c = c.clone();
+                c.sort();
+                c
+            })
+            .collect::<Vec<_>>();
+        components.sort();
+        assert_eq!(
+            components,
+            vec![vec![0, 1, 2], vec![2, 3], vec![3, 4], vec![5]]
+        );
+        let tree = block_cut_tree(6, lowlink.biconnected_components());
+        assert_eq!(tree.len(), 10);
+        assert_eq!(tree.n_edges(), 8);
+
+        let components = lowlink.two_edge_connected_components(&graph);
+        assert_eq!(components.len(), 3);
+        let tree = bridge_tree(&graph, &components);
+        assert_eq!(tree.n_edges(), 1);
+    }
+
+    #[test]
+    fn test_lowlink_long_path_does_not_overflow() {
+        let n = 1 << 17;
+        let graph = Graph::from_edges(n, false, (1..n).map(|v| (v - 1, v)));
+        let lowlink = Lowlink::new(&graph);
+        assert_eq!(lowlink.bridges().len(), n - 1);
+        assert_eq!(lowlink.articulation_points().len(), n - 2);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_lowlink_random(
+            (n, edges) in (1..12usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n), 0..20))
+            })
+        ) {
+            let graph = Graph::from_edges(n, false, edges.clone());
+            let lowlink = Lowlink::new(&graph);
+            let base = count_components(&graph, None, None);
+
+            let bridges = (0..edges.len())
+                .filter(|&id| count_components(&graph, None, Some(id)) > base)
+                .collect::<Vec<_>>();
+            assert_eq!(lowlink.bridges(), &bridges[..]);
+            let articulation_points = (0..n)
+                .filter(|&v| count_components(&graph, Some(v), None) > base)
+                .collect::<Vec<_>>();
+            assert_eq!(lowlink.articulation_points(), &articulation_points[..]);
+
+            // 二重辺連結成分では，同じ成分の節点は橋以外の辺でつながり，異なる成分を結ぶ辺は橋だけ．
+            let components = lowlink.two_edge_connected_components(&graph);
+            let ids = component_ids(n, &components);
+            for edge in graph.edges() {
+                assert_eq!(ids[edge.from] != ids[edge.to], bridges.contains(&edge.id));
+            }
+            let tree = bridge_tree(&graph, &components);
+            assert_eq!(tree.n_edges(), bridges.len());
+            assert_eq!(count_components(&Graph::from_edges(tree.len(), false, tree.edges().iter().map(|e| (e.from, e.to))), None, None), base);
+
+            // 二重頂点連結成分では，自己ループでない辺はちょうど一つの成分に含まれ，
+            // 二つ以上の成分に含まれる節点は関節点である．
+            let blocks = lowlink.biconnected_components();
+            for edge in graph.edges().iter().filter(|e| e.from != e.to) {
+                let count = blocks.iter().filter(|b| b.contains(&edge.from) && b.contains(&edge.to)).count();
+                assert_eq!(count, 1);
+            }
+            for v in 0..n {
+                let count = blocks.iter().filter(|b| b.contains(&v)).count();
+                assert!(count >= 1);
+                assert_eq!(count >= 2, articulation_points.contains(&v));
+            }
+            for block in blocks {
+                // 成分の中で一つの節点を取り除いても，残りは連結のまま．
+                let index = |v: usize| block.iter().position(|&u| u == v);
+                let induced = graph
+                    .edges()
+                    .iter()
+                    .filter_map(|e| Some((index(e.from)?, index(e.to)?)))
+                    .collect::<Vec<_>>();
+                let induced = Graph::from_edges(block.len(), false, induced);
+                assert_eq!(count_components(&induced, None, None), 1);
+                if block.len() >= 3 {
+                    for v in 0..block.len() {
+                        assert_eq!(count_components(&induced, Some(v), None), 1);
+                    }
+                }
+            }
+            let tree = block_cut_tree(n, blocks);
+            let tree = Graph::from_edges(tree.len(), false, tree.edges().iter().map(|e| (e.from, e.to)));
+            assert_eq!(tree.n_edges(), tree.len() - count_components(&tree, None, None));
+        }
+    }
+}
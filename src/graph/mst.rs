@@ -0,0 +1,164 @@
+use crate::data_structure::unionfind::UnionFind;
+use crate::graph::dijkstra::Cost;
+
+/// Kruskal 法で最小全域木を求める．`O(m log m)`．
+/// `edges` の各要素は (端点, 端点, 重み) で，節点は 0-indexed．
+/// (重みの総和, 選んだ辺の `edges` での添字の昇順) を返す．
+/// グラフが連結でなければ最小全域森を返すので，選んだ辺が `n - 1` 本かどうかで連結性を判定できる．
+pub fn kruskal<W>(n: usize, edges: &[(usize, usize, W)]) -> (W, Vec<usize>)
+where
+    W: Cost,
+{
+    let mut order = (0..edges.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| edges[i].2);
+    let mut uf = UnionFind::new(n);
+    let mut total = W::zero();
+    let mut chosen = vec![];
+    for i in order {
+        let (u, v, w) = edges[i];
+        if uf.unite(u, v) {
+            total = total.add_cost(w);
+            chosen.push(i);
+        }
+    }
+    chosen.sort();
+    (total, chosen)
+}
+
+/// Prim 法で最小全域木を求める．隣接行列を走査するので密なグラフに向く．`O(n^2)`．
+/// `matrix[u][v]` は節点 `u` と `v` を結ぶ辺の重みで，辺がなければ `None`．対称でなければならない．
+/// (重みの総和, 選んだ辺の端点の組) を返す．
+/// グラフが連結でなければ最小全域森を返す．
+pub fn prim<W>(matrix: &[Vec<Option<W>>]) -> (W, Vec<(usize, usize)>)
+where
+    W: Cost,
+{
+    let n = matrix.len();
+    let mut used = vec![false; n];
+    // i番目の要素は (木からi番目の節点への最小の重み, そのときの木側の端点).
+    let mut min_edge: Vec<Option<(W, usize)>> = vec![None; n];
+    let mut total = W::zero();
+    let mut chosen = vec![];
+    for _ in 0..n {
+        // 木に隣接する節点がなければ，未使用の節点から新しい木を始める．
+        let v = (0..n)
+            .filter(|&v| !used[v] && min_edge[v].is_some())
+            .min_by_key(|&v| min_edge[v].unwrap().0)
+            .or_else(|| (0..n).find(|&v| !used[v]))
+            .unwrap();
+        used[v] = true;
+        if let Some((w, u)) = min_edge[v] {
+            total = total.add_cost(w);
+            chosen.push((u, v));
+        }
+        for to in 0..n {
+            if let Some(w) = matrix[v][to] {
+                if !used[to] && min_edge[to].is_none_or(|(current, _)| current > w) {
+                    min_edge[to] = Some((w, v));
+                }
+            }
+        }
+    }
+    (total, chosen)
+}
+
+/// Borůvka 法で最小全域木を求める．各段階で成分ごとの最小の辺を一斉に選ぶ．`O(m log n)`．
+/// 引数と返り値は `kruskal` と同じで，辺が非常に多いときや並列化したいときに向く．
+pub fn boruvka<W>(n: usize, edges: &[(usize, usize, W)]) -> (W, Vec<usize>)
+where
+    W: Cost,
+{
+    let mut uf = UnionFind::new(n);
+    let mut total = W::zero();
+    let mut chosen = vec![];
+    loop {
+        // i番目の要素は，根がi番目の節点である成分から出る最小の辺の添字．
+        // 重みが等しい辺は添字で比べて，閉路ができないようにする．
+        let mut cheapest: Vec<Option<usize>> = vec![None; n];
+        for (i, &(u, v, w)) in edges.iter().enumerate() {
+            let (u_root, v_root) = (uf.root(u), uf.root(v));
+            if u_root == v_root {
+                continue;
+            }
+            for root in [u_root, v_root] {
+                if cheapest[root].is_none_or(|j| (edges[j].2, j) > (w, i)) {
+                    cheapest[root] = Some(i);
+                }
+            }
+        }
+        let mut merged = false;
+        for i in cheapest.into_iter().flatten() {
+            let (u, v, w) = edges[i];
+            if uf.unite(u, v) {
+                total = total.add_cost(w);
+                chosen.push(i);
+                merged = true;
+            }
+        }
+        if !merged {
+            break;
+        }
+    }
+    chosen.sort();
+    (total, chosen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::{Just, Strategy};
+
+    #[test]
+    fn test_minimum_spanning_tree() {
+        let edges = vec![(0, 1, 3), (1, 2, 1), (0, 2, 2), (2, 3, 5), (1, 3, 4)];
+        assert_eq!(kruskal(4, &edges), (7, vec![1, 2, 4]));
+        assert_eq!(boruvka(4, &edges), (7, vec![1, 2, 4]));
+
+        let mut matrix = vec![vec![None; 4]; 4];
+        for &(u, v, w) in &edges {
+            matrix[u][v] = Some(w);
+            matrix[v][u] = Some(w);
+        }
+        let (total, chosen) = prim(&matrix);
+        assert_eq!(total, 7);
+        assert_eq!(chosen, vec![(0, 2), (2, 1), (1, 3)]);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_minimum_spanning_tree_random(
+            (n, edges) in (1..20usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n, 0..20i64), 0..60))
+            })
+        ) {
+            let (total, chosen) = kruskal(n, &edges);
+
+            let mut uf = UnionFind::new(n);
+            for &(u, v, _) in &edges {
+                uf.unite(u, v);
+            }
+            let n_components = uf.count_components();
+            assert_eq!(chosen.len(), n - n_components);
+            let mut forest = UnionFind::new(n);
+            for &i in &chosen {
+                assert!(forest.unite(edges[i].0, edges[i].1));
+            }
+            assert_eq!(chosen.iter().map(|&i| edges[i].2).sum::<i64>(), total);
+
+            let (boruvka_total, boruvka_chosen) = boruvka(n, &edges);
+            assert_eq!(boruvka_total, total);
+            assert_eq!(boruvka_chosen.len(), chosen.len());
+
+            let mut matrix = vec![vec![None; n]; n];
+            for &(u, v, w) in &edges {
+                if u != v && matrix[u][v].is_none_or(|current| current > w) {
+                    matrix[u][v] = Some(w);
+                    matrix[v][u] = Some(w);
+                }
+            }
+            let (prim_total, prim_chosen) = prim(&matrix);
+            assert_eq!(prim_total, total);
+            assert_eq!(prim_chosen.len(), chosen.len());
+        }
+    }
+}
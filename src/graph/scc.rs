@@ -0,0 +1,163 @@
+use crate::graph::Graph;
+
+/// Tarjan 法で有向グラフを強連結成分分解する．`O(n + m)`．
+/// 再帰を使わないので深いグラフでもスタックオーバーフローしない．
+/// 成分はトポロジカル順に並び，`u` から `v` への辺があれば `u` の成分は `v` の成分より前か同じになる．
+pub fn scc<W>(graph: &Graph<W>) -> Vec<Vec<usize>> {
+    let n = graph.len();
+    let mut order = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut n_visited = 0;
+    // (節点, 次に調べる辺の添字) を積む．
+    let mut call_stack: Vec<(usize, usize)> = vec![];
+    for start in 0..n {
+        if order[start] != usize::MAX {
+            continue;
+        }
+        order[start] = n_visited;
+        low[start] = n_visited;
+        n_visited += 1;
+        stack.push(start);
+        on_stack[start] = true;
+        call_stack.push((start, 0));
+        while let Some((v, i)) = call_stack.last_mut() {
+            let v = *v;
+            if let Some(edge) = graph[v].get(*i) {
+                *i += 1;
+                let to = edge.to;
+                if order[to] == usize::MAX {
+                    order[to] = n_visited;
+                    low[to] = n_visited;
+                    n_visited += 1;
+                    stack.push(to);
+                    on_stack[to] = true;
+                    call_stack.push((to, 0));
+                } else if on_stack[to] {
+                    low[v] = low[v].min(order[to]);
+                }
+                continue;
+            }
+            call_stack.pop();
+            if let Some(&(parent, _)) = call_stack.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if low[v] == order[v] {
+                let mut component = vec![];
+                loop {
+                    let u = stack.pop().unwrap();
+                    on_stack[u] = false;
+                    component.push(u);
+                    if u == v {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    // Tarjan 法では成分がトポロジカル順の逆順に見つかる．
+    components.reverse();
+    components
+}
+
+/// `scc` の返り値から，各節点が属する成分の番号を求める．
+pub fn component_ids(n: usize, components: &[Vec<usize>]) -> Vec<usize> {
+    let mut ids = vec![0; n];
+    for (id, component) in components.iter().enumerate() {
+        for &v in component {
+            ids[v] = id;
+        }
+    }
+    ids
+}
+
+/// 強連結成分を一つの節点に縮約した DAG を作る．節点 `i` は `components[i]` に対応する．
+/// 異なる成分を結ぶ辺は重みを保ったまま残し，多重辺もまとめない．
+pub fn condensation<W>(graph: &Graph<W>, components: &[Vec<usize>]) -> Graph<W>
+where
+    W: Clone,
+{
+    let ids = component_ids(graph.len(), components);
+    let mut dag = Graph::directed(components.len());
+    for v in 0..graph.len() {
+        for edge in &graph[v] {
+            if ids[v] != ids[edge.to] {
+                dag.add_edge(ids[v], ids[edge.to], edge.weight.clone());
+            }
+        }
+    }
+    dag
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::{Just, Strategy};
+
+    #[test]
+    fn test_scc() {
+        let graph = Graph::from_edges(
+            6,
+            true,
+            vec![(1, 4), (5, 2), (3, 0), (5, 5), (4, 1), (0, 3), (4, 2)],
+        );
+        let mut components = scc(&graph);
+        components.iter_mut().for_each(|c| c.sort());
+        let ids = component_ids(6, &components);
+        assert_eq!(components.len(), 4);
+        assert!(ids[1] < ids[2]);
+        assert!(ids[5] < ids[2]);
+        assert_eq!(ids[1], ids[4]);
+        assert_eq!(ids[0], ids[3]);
+
+        let dag = condensation(&graph, &components);
+        assert_eq!(dag.len(), 4);
+        assert_eq!(dag.n_edges(), 2);
+    }
+
+    #[test]
+    fn test_scc_long_path_does_not_overflow() {
+        let n = 1_000_000;
+        let graph = Graph::from_edges(n, true, (0..n).map(|v| (v, (v + 1) % n)));
+        assert_eq!(scc(&graph).len(), 1);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_scc_random(
+            (n, edges) in (1..15usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n), 0..30))
+            })
+        ) {
+            let graph = Graph::from_edges(n, true, edges.clone());
+            // 推移閉包を求めて，互いに到達可能かどうかと比べる．
+            let mut reachable = vec![vec![false; n]; n];
+            for (v, row) in reachable.iter_mut().enumerate() {
+                row[v] = true;
+            }
+            for &(u, v) in &edges {
+                reachable[u][v] = true;
+            }
+            for k in 0..n {
+                for i in 0..n {
+                    for j in 0..n {
+                        reachable[i][j] |= reachable[i][k] && reachable[k][j];
+                    }
+                }
+            }
+            let components = scc(&graph);
+            let ids = component_ids(n, &components);
+            for u in 0..n {
+                for v in 0..n {
+                    assert_eq!(ids[u] == ids[v], reachable[u][v] && reachable[v][u]);
+                }
+            }
+            for &(u, v) in &edges {
+                assert!(ids[u] <= ids[v]);
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::graph::dijkstra::Cost;
+use crate::graph::Graph;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// 各節点に入る辺の本数を返す．
+fn in_degrees<W>(graph: &Graph<W>) -> Vec<usize> {
+    let mut in_degree = vec![0; graph.len()];
+    for v in 0..graph.len() {
+        for edge in &graph[v] {
+            in_degree[edge.to] += 1;
+        }
+    }
+    in_degree
+}
+
+/// 有向グラフをトポロジカルソートする．`O(n + m)`．
+/// 閉路があれば，閉路をなす節点の列 `v_0, v_1, ..., v_{k-1}` を `Err` で返す．
+/// このとき `v_i` から `v_{i+1}` へ，`v_{k-1}` から `v_0` へ辺がある．
+pub fn toposort<W>(graph: &Graph<W>) -> Result<Vec<usize>, Vec<usize>> {
+    let n = graph.len();
+    let mut in_degree = in_degrees(graph);
+    let mut order = (0..n).filter(|&v| in_degree[v] == 0).collect::<Vec<_>>();
+    let mut head = 0;
+    while let Some(&v) = order.get(head) {
+        head += 1;
+        for edge in &graph[v] {
+            in_degree[edge.to] -= 1;
+            if in_degree[edge.to] == 0 {
+                order.push(edge.to);
+            }
+        }
+    }
+    if order.len() == n {
+        return Ok(order);
+    }
+
+    // 残った節点はどれも残った節点から入る辺を持つので，辺を逆にたどればいずれ閉路に入る．
+    let mut prev = vec![None; n];
+    for v in 0..n {
+        for edge in &graph[v] {
+            if in_degree[v] > 0 && in_degree[edge.to] > 0 {
+                prev[edge.to] = Some(v);
+            }
+        }
+    }
+    let mut visited = vec![false; n];
+    let mut v = (0..n).find(|&v| in_degree[v] > 0).unwrap();
+    while !visited[v] {
+        visited[v] = true;
+        v = prev[v].unwrap();
+    }
+    let mut cycle = vec![v];
+    let mut u = prev[v].unwrap();
+    while u != v {
+        cycle.push(u);
+        u = prev[u].unwrap();
+    }
+    cycle.reverse();
+    Err(cycle)
+}
+
+/// 有向グラフのトポロジカル順序のうち辞書順最小のものを返す．閉路があれば `None` を返す．`O(n log n + m)`．
+pub fn lexicographic_toposort<W>(graph: &Graph<W>) -> Option<Vec<usize>> {
+    let n = graph.len();
+    let mut in_degree = in_degrees(graph);
+    let mut bin_heap = (0..n)
+        .filter(|&v| in_degree[v] == 0)
+        .map(Reverse)
+        .collect::<BinaryHeap<_>>();
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse(v)) = bin_heap.pop() {
+        order.push(v);
+        for edge in &graph[v] {
+            in_degree[edge.to] -= 1;
+            if in_degree[edge.to] == 0 {
+                bin_heap.push(Reverse(edge.to));
+            }
+        }
+    }
+    if order.len() == n {
+        Some(order)
+    } else {
+        None
+    }
+}
+
+/// DAG の各節点について，その節点で終わる経路の長さの最大値を求める．`O(n + m)`．
+/// 経路はどの節点から始めてもよく，辺を一本も通らない経路の長さは `C::zero()` とする．
+/// 閉路があれば `None` を返す．
+pub fn longest_path<C>(graph: &Graph<C>) -> Option<Vec<C>>
+where
+    C: Cost,
+{
+    let order = toposort(graph).ok()?;
+    let mut length = vec![C::zero(); graph.len()];
+    for v in order {
+        for edge in &graph[v] {
+            let candidate = length[v].add_cost(edge.weight);
+            if length[edge.to] < candidate {
+                length[edge.to] = candidate;
+            }
+        }
+    }
+    Some(length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::{Just, Strategy};
+
+    #[test]
+    fn test_toposort() {
+        let graph = Graph::from_edges(5, true, vec![(3, 1), (1, 0), (3, 4), (4, 0), (2, 4)]);
+        assert_eq!(lexicographic_toposort(&graph), Some(vec![2, 3, 1, 4, 0]));
+        let order = toposort(&graph).unwrap();
+        let mut position = [0; 5];
+        order.iter().enumerate().for_each(|(i, &v)| position[v] = i);
+        assert!(graph
+            .edges()
+            .iter()
+            .all(|e| position[e.from] < position[e.to]));
+
+        let graph = Graph::from_edges(4, true, vec![(0, 1), (1, 2), (2, 3), (3, 1)]);
+        let cycle = toposort(&graph).unwrap_err();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(lexicographic_toposort(&graph), None);
+        let weighted = Graph::from_edges(2, true, vec![(0, 1, 1i64), (1, 0, 1)]);
+        assert_eq!(longest_path(&weighted), None);
+
+        let graph = Graph::from_edges(1, true, vec![(0, 0)]);
+        assert_eq!(toposort(&graph), Err(vec![0]));
+    }
+
+    #[test]
+    fn test_longest_path() {
+        let graph = Graph::from_edges(
+            5,
+            true,
+            vec![(0, 1, 2i64), (0, 2, 1), (2, 1, 3), (1, 3, 1), (4, 3, 10)],
+        );
+        assert_eq!(longest_path(&graph), Some(vec![0, 4, 1, 10, 0]));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_toposort_random(
+            (n, edges) in (1..10usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, 0..n, 0..10i64), 0..20))
+            })
+        ) {
+            let graph = Graph::from_edges(n, true, edges.clone());
+            match toposort(&graph) {
+                Ok(order) => {
+                    let mut position = vec![n; n];
+                    order.iter().enumerate().for_each(|(i, &v)| position[v] = i);
+                    assert!(position.iter().all(|&p| p < n));
+                    assert!(edges.iter().all(|&(u, v, _)| position[u] < position[v]));
+
+                    // 辞書順最小のものは，すべての順列のうち条件を満たす最初のものと一致する．
+                    let lexicographic = lexicographic_toposort(&graph).unwrap();
+                    let mut position = vec![0; n];
+                    lexicographic.iter().enumerate().for_each(|(i, &v)| position[v] = i);
+                    assert!(edges.iter().all(|&(u, v, _)| position[u] < position[v]));
+                    for i in 0..n {
+                        // i 番目に置けた節点のうち最小のものが選ばれている．
+                        let placed = &lexicographic[..i];
+                        let smallest = (0..n)
+                            .filter(|v| !placed.contains(v))
+                            .find(|&v| edges.iter().all(|&(from, to, _)| to != v || placed.contains(&from)))
+                            .unwrap();
+                        assert_eq!(lexicographic[i], smallest);
+                    }
+
+                    // 長さを節点数だけ緩和を繰り返して求めたものと比べる．
+                    let mut expected = vec![0; n];
+                    for _ in 0..n {
+                        for &(u, v, w) in &edges {
+                            expected[v] = expected[v].max(expected[u] + w);
+                        }
+                    }
+                    assert_eq!(longest_path(&graph), Some(expected));
+                }
+                Err(cycle) => {
+                    assert!(!cycle.is_empty());
+                    for i in 0..cycle.len() {
+                        let (u, v) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+                        assert!(edges.iter().any(|&(from, to, _)| (from, to) == (u, v)));
+                    }
+                    let mut distinct = cycle.clone();
+                    distinct.sort();
+                    distinct.dedup();
+                    assert_eq!(distinct.len(), cycle.len());
+                    assert_eq!(lexicographic_toposort(&graph), None);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+pub mod centroid_decomposition;
+pub mod hld;
+pub mod lca;
+pub mod rerooting;
+#[cfg(test)]
+pub(crate) mod testing;
+
+pub use centroid_decomposition::centroid_decomposition;
+pub use hld::{Hld, HldSegmentTree};
+pub use lca::{EulerTourLca, Lca};
+pub use rerooting::rerooting;
+
+use crate::graph::Graph;
+
+/// 根つき木を深さ優先探索し，(各節点の親, 各節点の深さ, 行きがけ順に並べた節点) を返す．
+/// 再帰を使わないので深い木でもスタックオーバーフローしない．
+pub(super) fn dfs<W>(tree: &Graph<W>, root: usize) -> (Vec<Option<usize>>, Vec<usize>, Vec<usize>) {
+    let n = tree.len();
+    let mut parent = vec![None; n];
+    let mut depth = vec![0; n];
+    let mut preorder = Vec::with_capacity(n);
+    let mut stack = vec![root];
+    while let Some(v) = stack.pop() {
+        preorder.push(v);
+        // 隣接リストの順に訪れるよう逆順に積む．
+        for edge in tree[v].iter().rev() {
+            if Some(edge.to) != parent[v] {
+                parent[edge.to] = Some(v);
+                depth[edge.to] = depth[v] + 1;
+                stack.push(edge.to);
+            }
+        }
+    }
+    (parent, depth, preorder)
+}
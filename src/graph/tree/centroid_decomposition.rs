@@ -0,0 +1,234 @@
+use crate::graph::{Edge, Graph};
+
+/// 重心分解の結果．重心分解木 (各成分の重心を，その成分を含む一つ上の成分の重心の子としたもの) を表す．
+pub struct CentroidDecomposition {
+    parent: Vec<Option<usize>>,
+    depth: Vec<usize>,
+    // 重心を見つけた順．重心分解木の根から幅優先に近い順になり，親は子より前に現れる．
+    order: Vec<usize>,
+}
+
+/// 木 `tree` を重心分解する．`O(n log n)`．
+/// 重心分解木の深さは `log2 n` 以下になる．`tree` は連結でなければならない．
+pub fn centroid_decomposition<W>(tree: &Graph<W>) -> CentroidDecomposition {
+    let n = tree.len();
+    let mut removed = vec![false; n];
+    let mut parent = vec![None; n];
+    let mut depth = vec![0; n];
+    let mut order = Vec::with_capacity(n);
+    // 木内の親を覚えながら成分をたどり，部分木の大きさを求める．
+    let mut tree_parent = vec![usize::MAX; n];
+    let mut size = vec![0; n];
+    // (成分内の節点, 重心分解木での親) を積む．
+    let mut stack = if n > 0 { vec![(0, None)] } else { vec![] };
+    while let Some((start, centroid_parent)) = stack.pop() {
+        let mut component = vec![start];
+        tree_parent[start] = usize::MAX;
+        let mut head = 0;
+        while let Some(&v) = component.get(head) {
+            head += 1;
+            for edge in &tree[v] {
+                if !removed[edge.to] && edge.to != tree_parent[v] {
+                    tree_parent[edge.to] = v;
+                    component.push(edge.to);
+                }
+            }
+        }
+        for &v in component.iter().rev() {
+            size[v] = 1 + tree[v]
+                .iter()
+                .filter(|e| !removed[e.to] && e.to != tree_parent[v])
+                .map(|e| size[e.to])
+                .sum::<usize>();
+        }
+        // 根から重い方の子へ進み，子の部分木がどれも半分以下になる節点が重心．
+        let total = component.len();
+        let mut centroid = start;
+        while let Some(heavy) = tree[centroid]
+            .iter()
+            .find(|e| !removed[e.to] && e.to != tree_parent[centroid] && 2 * size[e.to] > total)
+        {
+            centroid = heavy.to;
+        }
+
+        removed[centroid] = true;
+        parent[centroid] = centroid_parent;
+        depth[centroid] = centroid_parent.map_or(0, |p| depth[p] + 1);
+        order.push(centroid);
+        for edge in &tree[centroid] {
+            if !removed[edge.to] {
+                stack.push((edge.to, Some(centroid)));
+            }
+        }
+    }
+    CentroidDecomposition {
+        parent,
+        depth,
+        order,
+    }
+}
+
+impl CentroidDecomposition {
+    /// 重心分解木の根を返す．
+    pub fn root(&self) -> usize {
+        self.order[0]
+    }
+
+    /// 重心分解木での `v` 番目の節点の親を返す．根なら `None` を返す．
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parent[v]
+    }
+
+    /// 重心分解木での `v` 番目の節点の深さを返す．
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// 重心を見つけた順に並べたものを返す．親は子より前に現れる．
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// 重心ごとに，その重心を取り除いたときに成分に残る部分木を列挙する．全体で `O(n log n)`．
+    /// 各要素は `(重心, 部分木の列)` で，部分木は重心から幅優先にたどった辺の列として表す．
+    /// 辺の `from` は重心に近い側の端点で，最初の辺は重心から出る．`to` を集めると部分木の節点になる．
+    /// 重心を端点の一つとするパス，および異なる部分木の節点を結ぶパスは，
+    /// いずれかの重心でちょうど一度ずつ現れるので，パスの数え上げに使える．
+    pub fn subtrees<'a, W>(
+        &'a self,
+        tree: &'a Graph<W>,
+    ) -> impl Iterator<Item = (usize, Vec<Vec<&'a Edge<W>>>)> + 'a {
+        self.order.iter().map(move |&centroid| {
+            // 成分に残っている節点は，重心分解木でこの重心より深い節点．
+            let depth = self.depth[centroid];
+            let subtrees = tree[centroid]
+                .iter()
+                .filter(|e| self.depth[e.to] > depth)
+                .map(|first| {
+                    let mut edges = vec![first];
+                    let mut head = 0;
+                    while let Some(&edge) = edges.get(head) {
+                        head += 1;
+                        edges.extend(
+                            tree[edge.to]
+                                .iter()
+                                .filter(|e| e.to != edge.from && self.depth[e.to] > depth),
+                        );
+                    }
+                    edges
+                })
+                .collect();
+            (centroid, subtrees)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::{Just, Strategy};
+
+    /// 距離がちょうど `k` の節点の組 (順序なし) の個数を重心分解で数える．
+    fn count_pairs(tree: &Graph<u32>, k: u32) -> usize {
+        let decomposition = centroid_decomposition(tree);
+        let mut count = 0;
+        for (_, subtrees) in decomposition.subtrees(tree) {
+            // これまでの部分木と重心から各距離にある節点の個数．
+            let mut seen = vec![0; k as usize + 1];
+            seen[0] = 1;
+            for edges in subtrees {
+                let mut distance = std::collections::HashMap::new();
+                let mut distances = vec![];
+                for edge in edges {
+                    let d = distance.get(&edge.from).copied().unwrap_or(0) + edge.weight;
+                    distance.insert(edge.to, d);
+                    distances.push(d);
+                }
+                for &d in &distances {
+                    if d <= k {
+                        count += seen[(k - d) as usize];
+                    }
+                }
+                for &d in &distances {
+                    if d <= k {
+                        seen[d as usize] += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn test_centroid_decomposition() {
+        // 0 - 1 - 2 - 3 - 4 - 5 - 6
+        let tree = Graph::from_edges(7, false, (0..6).map(|v| (v, v + 1, 1u32)));
+        let decomposition = centroid_decomposition(&tree);
+        assert_eq!(decomposition.root(), 3);
+        assert_eq!(decomposition.parent(1), Some(3));
+        assert_eq!(decomposition.parent(0), Some(1));
+        assert_eq!(decomposition.depth(6), 2);
+        assert_eq!(count_pairs(&tree, 2), 5);
+    }
+
+    #[test]
+    fn test_centroid_decomposition_long_path_does_not_overflow() {
+        let n = 1 << 16;
+        let tree = Graph::from_edges(n, false, (1..n).map(|v| (v - 1, v)));
+        let decomposition = centroid_decomposition(&tree);
+        assert!((0..n).all(|v| decomposition.depth(v) <= 16));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_centroid_decomposition_random(
+            (n, edges) in (1..40usize).prop_flat_map(|n| {
+                let edges = (1..n).map(|v| (0..v, 1..4u32)).collect::<Vec<_>>();
+                (Just(n), edges)
+            })
+        ) {
+            let tree = Graph::from_edges(n, false, edges.iter().enumerate().map(|(i, &(p, w))| (i + 1, p, w)));
+            let decomposition = centroid_decomposition(&tree);
+            assert_eq!(decomposition.order().len(), n);
+            for (i, &v) in decomposition.order().iter().enumerate() {
+                if let Some(p) = decomposition.parent(v) {
+                    assert!(decomposition.order()[..i].contains(&p));
+                    assert_eq!(decomposition.depth(v), decomposition.depth(p) + 1);
+                }
+            }
+            // 成分の大きさは重心分解木の部分木の大きさで，各部分木は成分の半分以下．
+            let mut component_size = vec![1; n];
+            for &v in decomposition.order().iter().rev() {
+                if let Some(p) = decomposition.parent(v) {
+                    component_size[p] += component_size[v];
+                }
+            }
+            for (centroid, subtrees) in decomposition.subtrees(&tree) {
+                let total = subtrees.iter().map(|s| s.len()).sum::<usize>() + 1;
+                assert_eq!(total, component_size[centroid]);
+                for edges in subtrees {
+                    assert!(2 * edges.len() <= total);
+                }
+            }
+
+            // 全点対の距離を求めて，重心分解で数えたものと比べる．
+            let mut distance = vec![vec![u32::MAX; n]; n];
+            for (s, row) in distance.iter_mut().enumerate() {
+                row[s] = 0;
+                let mut stack = vec![s];
+                while let Some(v) = stack.pop() {
+                    for edge in &tree[v] {
+                        if row[edge.to] == u32::MAX {
+                            row[edge.to] = row[v] + edge.weight;
+                            stack.push(edge.to);
+                        }
+                    }
+                }
+            }
+            for k in 1..8 {
+                let expected = (0..n).map(|u| (u + 1..n).filter(|&v| distance[u][v] == k).count()).sum::<usize>();
+                assert_eq!(count_pairs(&tree, k), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use crate::data_structure::segment_tree::SegmentTree;
+use crate::graph::tree::dfs;
+use crate::graph::Graph;
+use crate::math::monoid::Monoid;
+
+/// 重軽分解 (HL 分解)．根つき木の節点に番号を振り直し，パスを `O(log n)` 個の区間に，部分木を一つの区間に対応させる．
+/// 番号は重い子を先にたどる行きがけ順で，同じ heavy path 上の節点や同じ部分木の節点は連続した番号になる．
+/// 辺の値を扱うときは，辺の子の側の節点の番号に値を置く．
+pub struct Hld {
+    parent: Vec<Option<usize>>,
+    depth: Vec<usize>,
+    size: Vec<usize>,
+    // 節点が属する heavy path の最も根に近い節点．
+    head: Vec<usize>,
+    index: Vec<usize>,
+    // `order[i]` は番号が `i` の節点．
+    order: Vec<usize>,
+}
+
+impl Hld {
+    /// `root` 番目の節点を根とする木 `tree` を分解する．`tree` は連結でなければならない．`O(n)`．
+    pub fn new<W>(tree: &Graph<W>, root: usize) -> Self {
+        let n = tree.len();
+        let (parent, depth, preorder) = dfs(tree, root);
+        let mut size = vec![1; n];
+        for &v in preorder.iter().rev() {
+            if let Some(p) = parent[v] {
+                size[p] += size[v];
+            }
+        }
+
+        let mut head = vec![root; n];
+        let mut index = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            index[v] = order.len();
+            order.push(v);
+            let children = tree[v]
+                .iter()
+                .map(|e| e.to)
+                .filter(|&to| Some(to) != parent[v]);
+            let heavy = children.clone().max_by_key(|&to| size[to]);
+            // 重い子を最後に積んで，次にたどるようにする．
+            for to in children.filter(|&to| Some(to) != heavy) {
+                head[to] = to;
+                stack.push(to);
+            }
+            if let Some(heavy) = heavy {
+                head[heavy] = head[v];
+                stack.push(heavy);
+            }
+        }
+        Self {
+            parent,
+            depth,
+            size,
+            head,
+            index,
+            order,
+        }
+    }
+
+    /// 節点数を返す．
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// 節点を持たないかどうかを返す．
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// `v` 番目の節点の親を返す．根なら `None` を返す．
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parent[v]
+    }
+
+    /// `v` 番目の節点の根からの深さを返す．
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// `v` 番目の節点に振った番号を返す．
+    pub fn index(&self, v: usize) -> usize {
+        self.index[v]
+    }
+
+    /// 番号が `i` の節点を返す．
+    pub fn vertex(&self, i: usize) -> usize {
+        self.order[i]
+    }
+
+    /// `u` 番目の節点と `v` 番目の節点を結ぶ辺の値を置く番号を返す．二つの節点は隣接していなければならない．
+    pub fn edge_index(&self, u: usize, v: usize) -> usize {
+        if self.parent[u] == Some(v) {
+            self.index[u]
+        } else {
+            self.index[v]
+        }
+    }
+
+    /// `u` 番目の節点と `v` 番目の節点の最小共通祖先を返す．`O(log n)`．
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = (u, v);
+        while self.head[u] != self.head[v] {
+            if self.index[self.head[u]] > self.index[self.head[v]] {
+                u = self.parent[self.head[u]].unwrap();
+            } else {
+                v = self.parent[self.head[v]].unwrap();
+            }
+        }
+        if self.depth[u] <= self.depth[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    /// `u` 番目の節点と `v` 番目の節点の間の辺の本数を返す．
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    /// `u` 番目の節点から `v` 番目の節点へのパスを番号の区間に分解する．`O(log n)`．
+    /// 各要素は `(start, end, reversed)` で，パスを `u` から順にたどったときの区間 `[start, end)` を表す．
+    /// `reversed` が真なら，その区間は番号の大きい方から小さい方へたどる．
+    /// `edge` が真なら辺の値を扱い，最小共通祖先の番号を除く．
+    pub fn path(&self, u: usize, v: usize, edge: bool) -> Vec<(usize, usize, bool)> {
+        let (mut u, mut v) = (u, v);
+        let mut up = vec![];
+        let mut down = vec![];
+        while self.head[u] != self.head[v] {
+            if self.index[self.head[u]] > self.index[self.head[v]] {
+                up.push((self.index[self.head[u]], self.index[u] + 1, true));
+                u = self.parent[self.head[u]].unwrap();
+            } else {
+                down.push((self.index[self.head[v]], self.index[v] + 1, false));
+                v = self.parent[self.head[v]].unwrap();
+            }
+        }
+        let skip = usize::from(edge);
+        if self.index[u] >= self.index[v] {
+            up.push((self.index[v] + skip, self.index[u] + 1, true));
+        } else {
+            down.push((self.index[u] + skip, self.index[v] + 1, false));
+        }
+        up.extend(down.into_iter().rev());
+        up.retain(|&(start, end, _)| start < end);
+        up
+    }
+
+    /// `v` 番目の節点を根とする部分木に対応する番号の区間 `[start, end)` を返す．
+    /// `edge` が真なら辺の値を扱い，`v` 自身の番号を除く．
+    pub fn subtree(&self, v: usize, edge: bool) -> (usize, usize) {
+        let start = self.index[v];
+        (start + usize::from(edge), start + self.size[v])
+    }
+}
+
+/// `Hld` の番号に沿ってセグメント木を持ち，パスや部分木の値の積を求める．
+/// 可換でないモノイドでもパスの向きどおりに積を取れるよう，逆順に並べたセグメント木も持つ．
+pub struct HldSegmentTree<T>
+where
+    T: Monoid,
+{
+    hld: Hld,
+    edge: bool,
+    forward: SegmentTree<T>,
+    backward: SegmentTree<T>,
+}
+
+impl<T> HldSegmentTree<T>
+where
+    T: Monoid,
+{
+    /// `values[v]` を `v` 番目の節点の値として作る．
+    /// `edge` が真なら `values[v]` は `v` 番目の節点とその親を結ぶ辺の値とし，根の値は使わない．
+    pub fn new(hld: Hld, values: Vec<T>, edge: bool) -> Self {
+        let n = hld.len();
+        let mut values = values.into_iter().map(Some).collect::<Vec<_>>();
+        let ordered = (0..n)
+            .map(|i| values[hld.vertex(i)].take().unwrap())
+            .collect::<Vec<_>>();
+        let backward = ordered.iter().rev().cloned().collect();
+        Self {
+            hld,
+            edge,
+            forward: ordered.into(),
+            backward,
+        }
+    }
+
+    /// 分解に使った `Hld` を返す．
+    pub fn hld(&self) -> &Hld {
+        &self.hld
+    }
+
+    /// `v` 番目の節点の値を `x` にする．辺の値を扱うなら，`v` 番目の節点とその親を結ぶ辺の値を `x` にする．
+    pub fn update(&mut self, v: usize, x: T) {
+        let i = self.hld.index(v);
+        self.backward.update(self.hld.len() - 1 - i, x.clone());
+        self.forward.update(i, x);
+    }
+
+    /// `u` 番目の節点から `v` 番目の節点へのパス上の値を，`u` の側から順に掛けたものを返す．`O(log^2 n)`．
+    pub fn path(&self, u: usize, v: usize) -> T {
+        let n = self.hld.len();
+        self.hld.path(u, v, self.edge).into_iter().fold(
+            T::mempty(),
+            |acc, (start, end, reversed)| {
+                let x = if reversed {
+                    self.backward.query(n - end, n - start)
+                } else {
+                    self.forward.query(start, end)
+                };
+                acc.mappend(&x)
+            },
+        )
+    }
+
+    /// `v` 番目の節点を根とする部分木の値を，`Hld` の番号の順に掛けたものを返す．`O(log n)`．
+    pub fn subtree(&self, v: usize) -> T {
+        let (start, end) = self.hld.subtree(v, self.edge);
+        self.forward.query(start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::tree::testing::{naive_path, random_tree};
+    use proptest::strategy::{Just, Strategy};
+
+    /// 列の連結．可換でないモノイドの例．
+    #[derive(Clone, Debug, PartialEq)]
+    struct Concat(Vec<usize>);
+
+    impl Monoid for Concat {
+        fn mempty() -> Self {
+            Concat(vec![])
+        }
+
+        fn mappend(&self, other: &Self) -> Self {
+            Concat([&self.0[..], &other.0[..]].concat())
+        }
+    }
+
+    #[test]
+    fn test_hld() {
+        // 0 - 1 - 3
+        //  \   \
+        //   2   4 - 5
+        let tree = Graph::from_edges(6, false, vec![(0, 1), (0, 2), (1, 3), (1, 4), (4, 5)]);
+        let hld = Hld::new(&tree, 0);
+        assert_eq!(hld.lca(3, 5), 1);
+        assert_eq!(hld.dist(2, 5), 4);
+        let (start, end) = hld.subtree(1, false);
+        let mut subtree = (start..end).map(|i| hld.vertex(i)).collect::<Vec<_>>();
+        subtree.sort();
+        assert_eq!(subtree, vec![1, 3, 4, 5]);
+
+        let values = (0..6).map(|v| Concat(vec![v])).collect();
+        let mut st = HldSegmentTree::new(hld, values, false);
+        assert_eq!(st.path(5, 2), Concat(vec![5, 4, 1, 0, 2]));
+        assert_eq!(st.path(3, 5), Concat(vec![3, 1, 4, 5]));
+        st.update(4, Concat(vec![10]));
+        assert_eq!(st.path(0, 5), Concat(vec![0, 1, 10, 5]));
+
+        let hld = Hld::new(&tree, 0);
+        let values = (0..6).map(|v| Concat(vec![v])).collect();
+        let st = HldSegmentTree::new(hld, values, true);
+        // 辺の値はその子の側の節点に置かれる．
+        assert_eq!(st.path(5, 2), Concat(vec![5, 4, 1, 2]));
+        assert_eq!(st.path(4, 4), Concat(vec![]));
+        assert_eq!(st.hld().edge_index(1, 4), st.hld().index(4));
+    }
+
+    #[test]
+    fn test_hld_long_path_does_not_overflow() {
+        let n = 1_000_000;
+        let tree = Graph::from_edges(n, false, (1..n).map(|v| (v - 1, v)));
+        let hld = Hld::new(&tree, 0);
+        assert_eq!(hld.path(n - 1, 0, false), vec![(0, n, true)]);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_hld_random(
+            (tree, root) in random_tree(1..30, Just(())).prop_flat_map(|tree| {
+                let n = tree.len();
+                (Just(tree), 0..n)
+            })
+        ) {
+            let n = tree.len();
+            let values = (0..n).map(|v| Concat(vec![v])).collect::<Vec<_>>();
+            let vertex_st = HldSegmentTree::new(Hld::new(&tree, root), values.clone(), false);
+            let edge_st = HldSegmentTree::new(Hld::new(&tree, root), values, true);
+            let hld = vertex_st.hld();
+            for u in 0..n {
+                for v in 0..n {
+                    let path = naive_path(&tree, u, v);
+                    assert_eq!(hld.lca(u, v), *path.iter().min_by_key(|&&w| hld.depth(w)).unwrap());
+                    assert_eq!(hld.dist(u, v), path.len() - 1);
+                    assert_eq!(vertex_st.path(u, v).0, path);
+                    // 辺の値は深い方の端点に置かれる．
+                    let edges = path
+                        .windows(2)
+                        .map(|w| if hld.parent(w[0]) == Some(w[1]) { w[0] } else { w[1] })
+                        .collect::<Vec<_>>();
+                    assert_eq!(edge_st.path(u, v).0, edges);
+                }
+                // 部分木の節点は `u` から根へのパスに `u` を含むもの．
+                let mut expected = (0..n)
+                    .filter(|&w| naive_path(&tree, w, root).contains(&u))
+                    .collect::<Vec<_>>();
+                let mut actual = vertex_st.subtree(u).0;
+                actual.sort();
+                assert_eq!(&actual, &expected);
+                expected.retain(|&w| w != u);
+                let mut actual = edge_st.subtree(u).0;
+                actual.sort();
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+}
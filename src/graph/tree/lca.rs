@@ -0,0 +1,291 @@
+use crate::data_structure::sparse_table::SparseTable;
+use crate::graph::tree::dfs;
+use crate::graph::Graph;
+use crate::math::monoid::Min;
+
+/// ダブリングで根つき木の最小共通祖先を求める．前処理 `O(n log n)`，クエリ `O(log n)`．
+/// 木は無向グラフとして与え，辺の重みは使わない．距離は辺の本数で数える．
+pub struct Lca {
+    depth: Vec<usize>,
+    // `ancestor[k][v]` は `v` の `2^k` 個上の祖先．根を越える場合は根になる．
+    ancestor: Vec<Vec<usize>>,
+}
+
+impl Lca {
+    /// `root` 番目の節点を根とする木 `tree` から作る．`tree` は連結でなければならない．
+    pub fn new<W>(tree: &Graph<W>, root: usize) -> Self {
+        let (parent, depth, _) = dfs(tree, root);
+        let n = tree.len();
+        let mut ancestor = vec![parent.iter().map(|p| p.unwrap_or(root)).collect::<Vec<_>>()];
+        let mut k = 1;
+        while (1 << k) < n {
+            let prev = &ancestor[k - 1];
+            let row = (0..n).map(|v| prev[prev[v]]).collect();
+            ancestor.push(row);
+            k += 1;
+        }
+        Self { depth, ancestor }
+    }
+
+    /// `v` 番目の節点の根からの深さを返す．
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// `u` 番目の節点の `k` 個上の祖先を返す．根を越えるなら `None` を返す．
+    pub fn kth_ancestor(&self, u: usize, k: usize) -> Option<usize> {
+        if k > self.depth[u] {
+            return None;
+        }
+        let mut u = u;
+        for (i, row) in self.ancestor.iter().enumerate() {
+            if k >> i & 1 == 1 {
+                u = row[u];
+            }
+        }
+        Some(u)
+    }
+
+    /// `u` 番目の節点と `v` 番目の節点の最小共通祖先を返す．
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (u, v) = if self.depth[u] >= self.depth[v] {
+            (u, v)
+        } else {
+            (v, u)
+        };
+        let mut u = self.kth_ancestor(u, self.depth[u] - self.depth[v]).unwrap();
+        let mut v = v;
+        if u == v {
+            return u;
+        }
+        for row in self.ancestor.iter().rev() {
+            if row[u] != row[v] {
+                u = row[u];
+                v = row[v];
+            }
+        }
+        self.ancestor[0][u]
+    }
+
+    /// `u` 番目の節点と `v` 番目の節点の間の辺の本数を返す．
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    /// `u` 番目の節点から `v` 番目の節点へのパス上で，`u` から `k` 本の辺を進んだ節点を返す．
+    /// パスの長さが `k` 未満なら `None` を返す．
+    pub fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let lca = self.lca(u, v);
+        let (up, down) = (
+            self.depth[u] - self.depth[lca],
+            self.depth[v] - self.depth[lca],
+        );
+        if k <= up {
+            self.kth_ancestor(u, k)
+        } else if k <= up + down {
+            self.kth_ancestor(v, up + down - k)
+        } else {
+            None
+        }
+    }
+}
+
+/// オイラーツアーと `SparseTable` で根つき木の最小共通祖先を求める．前処理 `O(n log n)`，クエリ `O(1)`．
+/// オイラーツアーから子から戻る部分を除き，行きがけ順で 2 番目以降の各節点の親の行きがけ順の番号を
+/// 並べた列 (長さ `n - 1`) の上に `u32` の `SparseTable` を作る．節点数は `2^32` 未満でなければならない．
+/// `kth_ancestor` と `jump` は深さごとの節点の列を二分探索するので `O(log n)`．
+/// 木は無向グラフとして与え，辺の重みは使わない．距離は辺の本数で数える．
+pub struct EulerTourLca {
+    depth: Vec<usize>,
+    // 各節点の行きがけ順の番号．
+    order: Vec<u32>,
+    // `preorder[i]` は行きがけ順で `i` 番目の節点．
+    preorder: Vec<u32>,
+    // 節点を (深さ, 行きがけ順) の順に並べたもので，深さ `d` の節点は `[depth_start[d], depth_start[d + 1])` にある．
+    by_depth: Vec<u32>,
+    depth_start: Vec<usize>,
+    // `i` 番目の要素は，行きがけ順で `i + 1` 番目の節点の親の行きがけ順の番号．
+    table: SparseTable<Min<u32>>,
+}
+
+impl EulerTourLca {
+    /// `root` 番目の節点を根とする木 `tree` から作る．`tree` は連結でなければならない．
+    pub fn new<W>(tree: &Graph<W>, root: usize) -> Self {
+        let (parent, depth, preorder) = dfs(tree, root);
+        let n = tree.len();
+        let mut order = vec![0; n];
+        for (i, &v) in preorder.iter().enumerate() {
+            order[v] = i as u32;
+        }
+        // 行きがけ順のまま深さで数え上げソートする．
+        let mut depth_start = vec![0; n + 1];
+        for &d in &depth {
+            depth_start[d + 1] += 1;
+        }
+        for d in 0..n {
+            depth_start[d + 1] += depth_start[d];
+        }
+        let mut by_depth = vec![0; n];
+        let mut next = depth_start.clone();
+        for &v in &preorder {
+            by_depth[next[depth[v]]] = v as u32;
+            next[depth[v]] += 1;
+        }
+        let table = preorder
+            .iter()
+            .skip(1)
+            .map(|&v| Min(order[parent[v].unwrap()]))
+            .collect();
+        Self {
+            depth,
+            order,
+            preorder: preorder.into_iter().map(|v| v as u32).collect(),
+            by_depth,
+            depth_start,
+            table,
+        }
+    }
+
+    /// `v` 番目の節点の根からの深さを返す．
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// `u` 番目の節点の `k` 個上の祖先を返す．根を越えるなら `None` を返す．
+    pub fn kth_ancestor(&self, u: usize, k: usize) -> Option<usize> {
+        let d = self.depth[u].checked_sub(k)?;
+        // 深さ `d` の節点のうち，行きがけ順で `u` 以前にある最後のものが祖先になる．
+        let candidates = &self.by_depth[self.depth_start[d]..self.depth_start[d + 1]];
+        let i = candidates.partition_point(|&v| self.order[v as usize] <= self.order[u]);
+        Some(candidates[i - 1] as usize)
+    }
+
+    /// `u` 番目の節点と `v` 番目の節点の最小共通祖先を返す．
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        if u == v {
+            return u;
+        }
+        let (start, end) = if self.order[u] < self.order[v] {
+            (self.order[u], self.order[v])
+        } else {
+            (self.order[v], self.order[u])
+        };
+        // 行きがけ順で `(start, end]` にある節点の親のうち，最も行きがけ順の番号が小さいものが答え．
+        let i = self.table.query(start as usize, end as usize).0;
+        self.preorder[i as usize] as usize
+    }
+
+    /// `u` 番目の節点と `v` 番目の節点の間の辺の本数を返す．
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    /// `u` 番目の節点から `v` 番目の節点へのパス上で，`u` から `k` 本の辺を進んだ節点を返す．
+    /// パスの長さが `k` 未満なら `None` を返す．
+    pub fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let lca = self.lca(u, v);
+        let (up, down) = (
+            self.depth[u] - self.depth[lca],
+            self.depth[v] - self.depth[lca],
+        );
+        if k <= up {
+            self.kth_ancestor(u, k)
+        } else if k <= up + down {
+            self.kth_ancestor(v, up + down - k)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::{Just, Strategy};
+
+    /// 親をたどってパスを求める．
+    fn naive_path(parent: &[Option<usize>], u: usize, v: usize) -> Vec<usize> {
+        let ancestors = |mut v: usize| {
+            let mut result = vec![v];
+            while let Some(p) = parent[v] {
+                result.push(p);
+                v = p;
+            }
+            result
+        };
+        let (mut from_u, mut from_v) = (ancestors(u), ancestors(v));
+        // 共通する祖先を末尾から取り除き，最後に取り除いたものが最小共通祖先になる．
+        let mut lca = None;
+        while from_u.last().is_some() && from_u.last() == from_v.last() {
+            lca = from_u.pop();
+            from_v.pop();
+        }
+        from_u.push(lca.unwrap());
+        from_u.extend(from_v.into_iter().rev());
+        from_u
+    }
+
+    #[test]
+    fn test_lca() {
+        // 0 - 1 - 3
+        //  \   \
+        //   2   4 - 5
+        let tree = Graph::from_edges(6, false, vec![(0, 1), (0, 2), (1, 3), (1, 4), (4, 5)]);
+        let lca = Lca::new(&tree, 0);
+        let euler = EulerTourLca::new(&tree, 0);
+        for (u, v, expected) in [(3, 5, 1), (5, 2, 0), (4, 5, 4), (3, 3, 3)] {
+            assert_eq!(lca.lca(u, v), expected);
+            assert_eq!(euler.lca(u, v), expected);
+        }
+        assert_eq!(lca.dist(3, 2), 3);
+        assert_eq!(euler.dist(5, 3), 3);
+        assert_eq!(lca.kth_ancestor(5, 2), Some(1));
+        assert_eq!(euler.kth_ancestor(5, 4), None);
+        assert_eq!(lca.jump(5, 2, 3), Some(0));
+        assert_eq!(euler.jump(2, 5, 2), Some(1));
+        assert_eq!(lca.jump(2, 5, 5), None);
+    }
+
+    #[test]
+    fn test_lca_long_path_does_not_overflow() {
+        let n = 1_000_000;
+        let tree = Graph::from_edges(n, false, (1..n).map(|v| (v - 1, v)));
+        let lca = Lca::new(&tree, 0);
+        assert_eq!(lca.lca(n - 1, n / 2), n / 2);
+        let euler = EulerTourLca::new(&tree, 0);
+        assert_eq!(euler.lca(n - 1, n / 2), n / 2);
+        assert_eq!(euler.kth_ancestor(n - 1, n - 1), Some(0));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_lca_random(
+            (n, parents, root) in (1..30usize).prop_flat_map(|n| {
+                let parents = (1..n).map(|v| 0..v).collect::<Vec<_>>();
+                (Just(n), parents, 0..n)
+            })
+        ) {
+            let tree = Graph::from_edges(n, false, parents.iter().enumerate().map(|(i, &p)| (i + 1, p)));
+            let (parent, _, _) = dfs(&tree, root);
+            let lca = Lca::new(&tree, root);
+            let euler = EulerTourLca::new(&tree, root);
+            for u in 0..n {
+                for v in 0..n {
+                    let path = naive_path(&parent, u, v);
+                    let expected_lca = *path.iter().min_by_key(|&&w| lca.depth(w)).unwrap();
+                    assert_eq!(lca.lca(u, v), expected_lca);
+                    assert_eq!(euler.lca(u, v), expected_lca);
+                    assert_eq!(lca.dist(u, v), path.len() - 1);
+                    assert_eq!(euler.dist(u, v), path.len() - 1);
+                    for k in 0..=path.len() {
+                        assert_eq!(lca.jump(u, v, k), path.get(k).cloned());
+                        assert_eq!(euler.jump(u, v, k), path.get(k).cloned());
+                    }
+                }
+                for k in 0..=n {
+                    assert_eq!(lca.kth_ancestor(u, k), euler.kth_ancestor(u, k));
+                }
+            }
+        }
+    }
+}
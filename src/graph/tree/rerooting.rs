@@ -0,0 +1,174 @@
+use crate::graph::{Edge, Graph};
+use crate::math::monoid::Monoid;
+
+/// 全方位木 DP．木の各節点を根としたときの DP の値をまとめて求める．`O(n)`．
+/// 節点 `v` を根とする部分木の値は，`v` の子 `c` それぞれについて `lift(&(c を根とする部分木の値), edge)`
+/// を求めて `mappend` で掛け合わせ，`add_root(&積, v)` を適用したものとする．
+/// `edge` は `v` から `c` へ向かう辺で，`edge.from` が根に近い側になる．
+/// 子を掛ける順序は定めないので，`mappend` は可換でなければならない．
+/// `tree` は連結な無向グラフでなければならない．
+pub fn rerooting<T, W, L, R>(tree: &Graph<W>, lift: L, add_root: R) -> Vec<T>
+where
+    T: Monoid,
+    L: Fn(&T, &Edge<W>) -> T,
+    R: Fn(&T, usize) -> T,
+{
+    let n = tree.len();
+    if n == 0 {
+        return vec![];
+    }
+    // 節点 0 を根として，行きがけ順と，各節点の親へ向かう辺の `tree[v]` での添字を求める．
+    let mut parent_edge = vec![None; n];
+    let mut preorder = Vec::with_capacity(n);
+    let mut visited = vec![false; n];
+    visited[0] = true;
+    let mut stack = vec![0];
+    while let Some(v) = stack.pop() {
+        preorder.push(v);
+        for edge in &tree[v] {
+            if !visited[edge.to] {
+                visited[edge.to] = true;
+                parent_edge[edge.to] = tree[edge.to].iter().position(|e| e.id == edge.id);
+                stack.push(edge.to);
+            }
+        }
+    }
+    let is_child = |v: usize, i: usize| parent_edge[v] != Some(i);
+
+    // 節点 0 を根としたときの，各節点を根とする部分木の値．
+    let mut down = vec![T::mempty(); n];
+    for &v in preorder.iter().rev() {
+        let product = tree[v]
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| is_child(v, i))
+            .fold(T::mempty(), |acc, (_, edge)| {
+                acc.mappend(&lift(&down[edge.to], edge))
+            });
+        down[v] = add_root(&product, v);
+    }
+
+    // `up[v]` は，`v` の親の側の部分木を `v` の子として見たときの `lift` を適用した値．
+    let mut up = vec![T::mempty(); n];
+    let mut result = vec![T::mempty(); n];
+    for &v in &preorder {
+        // `v` から出る辺ごとの値．親へ向かう辺には `up[v]` を使う．
+        let values = tree[v]
+            .iter()
+            .enumerate()
+            .map(|(i, edge)| {
+                if is_child(v, i) {
+                    lift(&down[edge.to], edge)
+                } else {
+                    up[v].clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        // 各辺を除いた積を累積積で求める．
+        let mut suffix = vec![T::mempty(); values.len() + 1];
+        for i in (0..values.len()).rev() {
+            suffix[i] = values[i].mappend(&suffix[i + 1]);
+        }
+        let mut prefix = T::mempty();
+        for (i, edge) in tree[v].iter().enumerate() {
+            if is_child(v, i) {
+                let excluded = add_root(&prefix.mappend(&suffix[i + 1]), v);
+                let back = &tree[edge.to][parent_edge[edge.to].unwrap()];
+                up[edge.to] = lift(&excluded, back);
+            }
+            prefix = prefix.mappend(&values[i]);
+        }
+        result[v] = add_root(&prefix, v);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::{Just, Strategy};
+
+    /// (節点数, 根からの距離の総和)．
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct DistanceSum(u64, u64);
+
+    impl Monoid for DistanceSum {
+        fn mempty() -> Self {
+            DistanceSum(0, 0)
+        }
+
+        fn mappend(&self, other: &Self) -> Self {
+            DistanceSum(self.0 + other.0, self.1 + other.1)
+        }
+    }
+
+    fn distance_sums(tree: &Graph<u64>) -> Vec<u64> {
+        rerooting(
+            tree,
+            |x: &DistanceSum, edge| DistanceSum(x.0, x.1 + x.0 * edge.weight),
+            |x, _| DistanceSum(x.0 + 1, x.1),
+        )
+        .into_iter()
+        .map(|x| x.1)
+        .collect()
+    }
+
+    #[test]
+    fn test_rerooting() {
+        // 0 - 1 - 2
+        //      \
+        //       3
+        let tree = Graph::from_edges(4, false, vec![(0, 1, 1u64), (1, 2, 2), (1, 3, 3)]);
+        assert_eq!(distance_sums(&tree), vec![8, 6, 10, 12]);
+
+        // 各節点から最も遠い節点までの辺の本数．
+        let tree = Graph::from_edges(5, false, vec![(0, 1), (1, 2), (2, 3), (1, 4)]);
+        let heights = rerooting(
+            &tree,
+            |x: &crate::math::monoid::Max<i64>, _| crate::math::monoid::Max(x.0 + 1),
+            |x, _| crate::math::monoid::Max(x.0.max(0)),
+        );
+        assert_eq!(
+            heights.into_iter().map(|x| x.0).collect::<Vec<_>>(),
+            vec![3, 2, 2, 3, 3]
+        );
+    }
+
+    #[test]
+    fn test_rerooting_long_path_does_not_overflow() {
+        let n = 1 << 17;
+        let tree = Graph::from_edges(n, false, (1..n).map(|v| (v - 1, v, 1u64)));
+        let sums = distance_sums(&tree);
+        let n = n as u64;
+        assert_eq!(sums[0], n * (n - 1) / 2);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_rerooting_random(
+            (n, edges) in (1..40usize).prop_flat_map(|n| {
+                let edges = (1..n).map(|v| (0..v, 0..5u64)).collect::<Vec<_>>();
+                (Just(n), edges)
+            })
+        ) {
+            let tree = Graph::from_edges(n, false, edges.iter().enumerate().map(|(i, &(p, w))| (i + 1, p, w)));
+            let expected = (0..n)
+                .map(|s| {
+                    let mut distance = vec![None; n];
+                    distance[s] = Some(0);
+                    let mut stack = vec![s];
+                    while let Some(v) = stack.pop() {
+                        for edge in &tree[v] {
+                            if distance[edge.to].is_none() {
+                                distance[edge.to] = Some(distance[v].unwrap() + edge.weight);
+                                stack.push(edge.to);
+                            }
+                        }
+                    }
+                    distance.into_iter().map(Option::unwrap).sum::<u64>()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(distance_sums(&tree), expected);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! 木のアルゴリズムのテストで共有する補助関数．
+
+use crate::graph::Graph;
+use proptest::strategy::{Just, Strategy};
+use std::fmt::Debug;
+use std::ops::Range;
+
+/// 節点数が `sizes` に含まれ，辺の重みが `weight` で生成される無向の木を生成する．
+/// 節点 `v > 0` は `v` 未満の節点のいずれかと結ばれる．
+pub fn random_tree<S>(sizes: Range<usize>, weight: S) -> impl Strategy<Value = Graph<S::Value>>
+where
+    S: Strategy + Clone,
+    S::Value: Clone + Debug,
+{
+    sizes.prop_flat_map(move |n| {
+        let edges = (1..n).map(|v| (0..v, weight.clone())).collect::<Vec<_>>();
+        (Just(n), edges).prop_map(|(n, edges)| {
+            let edges = edges
+                .into_iter()
+                .enumerate()
+                .map(|(i, (p, w))| (i + 1, p, w));
+            Graph::from_edges(n, false, edges)
+        })
+    })
+}
+
+/// 木 `tree` で `u` から `v` へのパス上の節点を順に並べて返す．
+pub fn naive_path<W>(tree: &Graph<W>, u: usize, v: usize) -> Vec<usize> {
+    // `v` を根として親をたどる．
+    let mut parent = vec![None; tree.len()];
+    let mut visited = vec![false; tree.len()];
+    visited[v] = true;
+    let mut stack = vec![v];
+    while let Some(w) = stack.pop() {
+        for edge in &tree[w] {
+            if !visited[edge.to] {
+                visited[edge.to] = true;
+                parent[edge.to] = Some(w);
+                stack.push(edge.to);
+            }
+        }
+    }
+    let mut path = vec![u];
+    while let Some(p) = parent[*path.last().unwrap()] {
+        path.push(p);
+    }
+    path
+}
@@ -0,0 +1,148 @@
+use crate::graph::scc::{component_ids, scc};
+use crate::graph::Graph;
+
+/// 2-SAT を強連結成分分解で解く．
+/// 変数 `x_i` が `f` であるという条件をリテラル `(i, f)` で表す．
+pub struct TwoSat {
+    n_variables: usize,
+    // 節 `(a || b)` の組．
+    clauses: Vec<((usize, bool), (usize, bool))>,
+}
+
+impl TwoSat {
+    /// 変数 `n` 個で節のない問題を作る．
+    pub fn new(n: usize) -> Self {
+        Self {
+            n_variables: n,
+            clauses: vec![],
+        }
+    }
+
+    /// 変数の個数を返す．
+    pub fn len(&self) -> usize {
+        self.n_variables
+    }
+
+    /// 変数を持たないかどうかを返す．
+    pub fn is_empty(&self) -> bool {
+        self.n_variables == 0
+    }
+
+    /// 変数を一つ追加し，その番号を返す．
+    pub fn add_variable(&mut self) -> usize {
+        self.n_variables += 1;
+        self.n_variables - 1
+    }
+
+    /// 節 `(x_i == f) || (x_j == g)` を加える．
+    pub fn add_clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        self.clauses.push(((i, f), (j, g)));
+    }
+
+    /// `(x_i == f)` ならば `(x_j == g)` という条件を加える．
+    pub fn add_implication(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        self.add_clause(i, !f, j, g);
+    }
+
+    /// `x_i == f` という条件を加える．
+    pub fn add_unit(&mut self, i: usize, f: bool) {
+        self.add_clause(i, f, i, f);
+    }
+
+    /// `x_i != x_j` という条件を加える．
+    pub fn add_xor(&mut self, i: usize, j: usize) {
+        self.add_clause(i, true, j, true);
+        self.add_clause(i, false, j, false);
+    }
+
+    /// `x_i == x_j` という条件を加える．
+    pub fn add_equal(&mut self, i: usize, j: usize) {
+        self.add_clause(i, true, j, false);
+        self.add_clause(i, false, j, true);
+    }
+
+    /// `literals` のうち真になるものが高々一つという条件を加える．
+    /// 補助変数を `literals.len()` 個追加し，節を `O(literals.len())` 個加える．
+    pub fn add_at_most_one(&mut self, literals: &[(usize, bool)]) {
+        // 補助変数 s_k は「k 番目までのリテラルのいずれかが真」を表す．
+        let mut prev: Option<usize> = None;
+        for &(i, f) in literals {
+            let s = self.add_variable();
+            self.add_implication(i, f, s, true);
+            if let Some(p) = prev {
+                self.add_implication(p, true, s, true);
+                self.add_implication(p, true, i, !f);
+            }
+            prev = Some(s);
+        }
+    }
+
+    /// すべての節を満たす割り当てがあれば，各変数の値を返す．
+    /// `add_variable` や `add_at_most_one` で追加した変数の値も含まれる．`O(n + m)`．
+    pub fn satisfiable(&self) -> Option<Vec<bool>> {
+        let n = self.n_variables;
+        // 節点 2i は x_i が真，節点 2i + 1 は x_i が偽であることを表す．
+        let node = |(i, f): (usize, bool)| 2 * i + if f { 0 } else { 1 };
+        let mut graph = Graph::directed(2 * n);
+        for &(a, b) in &self.clauses {
+            // (a || b) は (!a -> b) かつ (!b -> a)
+            graph.add_edge(node((a.0, !a.1)), node(b), ());
+            graph.add_edge(node((b.0, !b.1)), node(a), ());
+        }
+        let ids = component_ids(2 * n, &scc(&graph));
+        (0..n)
+            .map(|i| {
+                let (t, f) = (ids[node((i, true))], ids[node((i, false))]);
+                // トポロジカル順で後ろにあるリテラルを真にする．
+                if t == f {
+                    None
+                } else {
+                    Some(t > f)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::{Just, Strategy};
+
+    #[test]
+    fn test_two_sat_helpers() {
+        let mut two_sat = TwoSat::new(4);
+        two_sat.add_unit(0, true);
+        two_sat.add_implication(0, true, 1, false);
+        two_sat.add_xor(1, 2);
+        two_sat.add_at_most_one(&[(2, true), (3, true)]);
+        let assignment = two_sat.satisfiable().unwrap();
+        assert_eq!(&assignment[..4], &[true, false, true, false]);
+
+        two_sat.add_equal(2, 3);
+        assert_eq!(two_sat.satisfiable(), None);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_two_sat_random(
+            (n, clauses) in (1..8usize).prop_flat_map(|n| {
+                (Just(n), proptest::collection::vec((0..n, proptest::bool::ANY, 0..n, proptest::bool::ANY), 0..20))
+            })
+        ) {
+            let mut two_sat = TwoSat::new(n);
+            for &(i, f, j, g) in &clauses {
+                two_sat.add_clause(i, f, j, g);
+            }
+            let satisfies = |x: &[bool]| clauses.iter().all(|&(i, f, j, g)| x[i] == f || x[j] == g);
+            let exists = (0..1usize << n).any(|bits| {
+                let x = (0..n).map(|i| bits >> i & 1 == 1).collect::<Vec<_>>();
+                satisfies(&x)
+            });
+            match two_sat.satisfiable() {
+                Some(x) => assert!(satisfies(&x)),
+                None => assert!(!exists),
+            }
+        }
+    }
+}
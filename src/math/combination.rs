@@ -1,5 +1,4 @@
 #[allow(dead_code)]
-
 /// 組み合わせ,順列を求める.  
 /// `n (n < Mod)`を対象の集合の大きさとして前処理 `O(n)`，クエリ `O(1)`.
 pub struct Combination {
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
 /// 整数 `n` の素因数分解をする. `O(sqrt(n))`.
+// `is_multiple_of` は Rust 1.87 以降でしか使えないので剰余で判定する．
+#[allow(clippy::manual_is_multiple_of)]
 pub fn factorize(mut n: usize) -> HashMap<usize, usize> {
     let mut result = HashMap::new();
     let mut i = 2;
@@ -34,6 +36,7 @@ mod tests {
         );
     }
 
+    #[allow(clippy::manual_is_multiple_of)]
     fn is_prime(n: &usize) -> bool {
         let mut i = 2;
         while i * i <= *n {
@@ -42,7 +45,7 @@ mod tests {
             }
             i += 1;
         }
-        return true;
+        true
     }
 
     proptest::proptest! {
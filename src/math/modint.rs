@@ -71,12 +71,14 @@ impl std::ops::MulAssign for ModInt {
 
 impl std::ops::Div for ModInt {
     type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: Self) -> Self::Output {
         Self::new(self.value * rhs.inv().value)
     }
 }
 
 impl std::ops::DivAssign for ModInt {
+    #[allow(clippy::suspicious_op_assign_impl)]
     fn div_assign(&mut self, rhs: Self) {
         *self = ModInt::new(self.value * rhs.inv().value)
     }
@@ -103,6 +105,26 @@ impl std::cmp::Ord for ModInt {
     }
 }
 
+impl num::Zero for ModInt {
+    fn zero() -> Self {
+        Self::new(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+}
+
+impl num::FromPrimitive for ModInt {
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(Self::new((n % Self::MOD as i64) as isize))
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(Self::new((n % Self::MOD as u64) as isize))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::math::modint::ModInt;
@@ -38,3 +38,11 @@ where
         Min(self.0.min(other.0))
     }
 }
+
+/// 冪等なモノイド (`a.mappend(&a) == a`) であることを表す．
+/// 区間が重なっても結果が変わらないので，`SparseTable` で `O(1)` クエリが可能になる．
+pub trait Idempotent: Monoid {}
+
+impl<T> Idempotent for Max<T> where T: Copy + Ord + num::Bounded {}
+
+impl<T> Idempotent for Min<T> where T: Copy + Ord + num::Bounded {}